@@ -0,0 +1,235 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use tetromino::Direction;
+
+/// AutoShift repeats sideways moves while a direction is held. The first
+/// move happens on the press, the next once the button has been held for
+/// the delayed auto shift (DAS) delay and the rest every auto repeat rate
+/// (ARR) after that. Charge is kept when a new piece spawns.
+pub struct AutoShift {
+    delay: f64,
+    rate: f64,
+    cut: f64,
+    width: u32,
+    global_time: Rc<Cell<f64>>,
+    left_held: bool,
+    right_held: bool,
+    direction: Option<Direction>,
+    pressed_at: f64,
+    shifts: u32,
+    cut_until: f64,
+}
+
+impl AutoShift {
+    /// `delay` and `rate` are in seconds, where a rate of 0 moves pieces
+    /// straight to the wall. `cut` is how long auto shift is held back for
+    /// after a piece spawns. `width` is the width of the grid, the furthest
+    /// a piece can ever be shifted.
+    pub fn new(delay: f64,
+               rate: f64,
+               cut: f64,
+               width: u32,
+               global_time: Rc<Cell<f64>>)
+               -> AutoShift {
+        AutoShift {
+            delay: delay,
+            rate: rate,
+            cut: cut,
+            width: width,
+            global_time: global_time,
+            left_held: false,
+            right_held: false,
+            direction: None,
+            pressed_at: 0.0,
+            shifts: 0,
+            cut_until: 0.0,
+        }
+    }
+
+    /// The direction currently being shifted in, if any
+    pub fn direction(&self) -> Option<Direction> {
+        self.direction
+    }
+
+    /// A direction was pressed. It takes over from the other direction and
+    /// starts charging from scratch.
+    pub fn press(&mut self, direction: Direction) {
+        match direction {
+            Direction::West => self.left_held = true,
+            Direction::East => self.right_held = true,
+            _ => return,
+        }
+
+        self.charge(direction);
+    }
+
+    /// A direction was released. If the other direction is still held it
+    /// takes over, charging from scratch.
+    pub fn release(&mut self, direction: Direction) {
+        let other = match direction {
+            Direction::West => {
+                self.left_held = false;
+                self.right_held
+            }
+            Direction::East => {
+                self.right_held = false;
+                self.left_held
+            }
+            _ => return,
+        };
+
+        if self.direction == Some(direction) {
+            self.direction = None;
+            if other {
+                let opposite = match direction {
+                    Direction::West => Direction::East,
+                    _ => Direction::West,
+                };
+                self.charge(opposite);
+            }
+        }
+    }
+
+    /// Lets go of both directions
+    pub fn release_all(&mut self) {
+        self.left_held = false;
+        self.right_held = false;
+        self.direction = None;
+    }
+
+    /// A new piece has spawned
+    pub fn spawned(&mut self) {
+        self.cut_until = self.global_time.get() + self.cut;
+    }
+
+    /// The number of cells the piece should be shifted by since this was
+    /// last called. This is `u32::MAX` when the piece should move as far
+    /// as it can.
+    pub fn update(&mut self) -> u32 {
+        if self.direction.is_none() {
+            return 0;
+        }
+
+        let now = self.global_time.get();
+        let held_for = now - self.pressed_at;
+        if held_for < self.delay {
+            return 0;
+        }
+
+        let cut = now < self.cut_until;
+        if self.rate <= 0.0 {
+            return if cut { 0 } else { u32::max_value() };
+        }
+
+        // A piece can't be shifted further than the width of the grid, so
+        // counting stops there however small the rate is
+        let repeats = ((held_for - self.delay) / self.rate).min(self.width as f64);
+        let due = 1 + repeats as u32;
+
+        // Shifts that come due during the cut are skipped rather than saved
+        // up for later
+        let shifts = if cut { 0 } else { due - self.shifts };
+
+        self.shifts = due;
+        shifts
+    }
+
+    fn charge(&mut self, direction: Direction) {
+        self.direction = Some(direction);
+        self.pressed_at = self.global_time.get();
+        self.shifts = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use super::AutoShift;
+    use tetromino::Direction;
+
+    fn auto_shift(delay: f64, rate: f64, cut: f64) -> (AutoShift, Rc<Cell<f64>>) {
+        let time = Rc::new(Cell::new(0.0));
+        (AutoShift::new(delay, rate, cut, 10, time.clone()), time)
+    }
+
+    #[test]
+    fn repeats_after_the_delay_and_then_at_the_rate() {
+        let (mut auto_shift, time) = auto_shift(0.25, 0.125, 0.0);
+        auto_shift.press(Direction::East);
+
+        time.set(0.125);
+        assert_eq!(auto_shift.update(), 0);
+        time.set(0.25);
+        assert_eq!(auto_shift.update(), 1);
+        time.set(0.3125);
+        assert_eq!(auto_shift.update(), 0);
+        time.set(0.375);
+        assert_eq!(auto_shift.update(), 1);
+
+        // Ticks that cover several repeats get all of them at once
+        time.set(0.75);
+        assert_eq!(auto_shift.update(), 3);
+    }
+
+    #[test]
+    fn a_rate_of_zero_shifts_all_the_way() {
+        let (mut auto_shift, time) = auto_shift(0.25, 0.0, 0.0);
+        auto_shift.press(Direction::West);
+        assert_eq!(auto_shift.update(), 0);
+
+        time.set(0.25);
+        assert_eq!(auto_shift.update(), u32::max_value());
+    }
+
+    #[test]
+    fn tiny_rates_shift_no_further_than_the_grid() {
+        let (mut auto_shift, time) = auto_shift(0.25, 1e-300, 0.0);
+        auto_shift.press(Direction::East);
+
+        time.set(0.25);
+        assert_eq!(auto_shift.update(), 1);
+        time.set(1.0);
+        assert_eq!(auto_shift.update(), 10);
+        time.set(1000.0);
+        assert_eq!(auto_shift.update(), 0);
+    }
+
+    #[test]
+    fn the_cut_skips_shifts_after_a_spawn() {
+        let (mut auto_shift, time) = auto_shift(0.25, 0.125, 0.25);
+        auto_shift.press(Direction::East);
+
+        time.set(0.25);
+        assert_eq!(auto_shift.update(), 1);
+        auto_shift.spawned();
+
+        time.set(0.375);
+        assert_eq!(auto_shift.update(), 0);
+        time.set(0.5);
+        assert_eq!(auto_shift.update(), 1);
+    }
+
+    #[test]
+    fn releasing_hands_over_to_the_other_direction() {
+        let (mut auto_shift, time) = auto_shift(0.25, 0.125, 0.0);
+        auto_shift.press(Direction::West);
+        auto_shift.press(Direction::East);
+        assert!(auto_shift.direction() == Some(Direction::East));
+
+        time.set(0.125);
+        auto_shift.release(Direction::East);
+        assert!(auto_shift.direction() == Some(Direction::West));
+
+        // The other direction charges from scratch
+        time.set(0.25);
+        assert_eq!(auto_shift.update(), 0);
+        time.set(0.375);
+        assert_eq!(auto_shift.update(), 1);
+
+        auto_shift.release(Direction::West);
+        assert!(auto_shift.direction().is_none());
+    }
+}
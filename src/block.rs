@@ -0,0 +1,12 @@
+/// A single occupied cell, either locked in to the `Grid` or part of a
+/// `Tetromino`. Colors are RGBA with each channel in `0.0..1.0`.
+#[derive(Copy, Clone)]
+pub struct Block {
+    pub color: [f32; 4],
+}
+
+impl Block {
+    pub fn new(color: [f32; 4]) -> Block {
+        Block { color: color }
+    }
+}
@@ -0,0 +1,384 @@
+use std::cmp;
+use std::str::FromStr;
+
+use gravity::{GravityType, FRAME_RATE};
+use leveling::LevelingSystemType;
+use lock_delay::{LockReset, MOVE_RESET_LIMIT};
+use mode::GameModeType;
+use piece_set::PieceSetType;
+use randomizer::RandomizerType;
+use rotation::{HalfTurnKicks, RotationSystemType};
+use scoring::ScoringSystemType;
+
+pub const MAX_PREVIEW_LENGTH: usize = 6;
+
+/// The smallest grid that can be built. Anything smaller is grown to fit.
+pub const MIN_GRID_WIDTH: u32 = 4;
+pub const MIN_VISIBLE_ROWS: u32 = 4;
+
+/// Settings shared by the engine and the frontend. Build one with a
+/// `ConfigBuilder`.
+#[derive(Clone)]
+pub struct Config {
+    pub grid_offset: f64,
+    pub tile_size: f64,
+    /// The width and height of the grid, including the hidden rows
+    pub grid_size: (u32, u32),
+    /// The number of rows at the top of the grid that pieces spawn in
+    /// and that are never drawn
+    pub hidden_rows: u32,
+    pub ui_color: [f32; 4],
+    pub shadow_enabled: bool,
+    pub rotation_system: RotationSystemType,
+    /// The kicks tried when a piece is turned 180 degrees
+    pub half_turn_kicks: HalfTurnKicks,
+    pub pieces: PieceSetType,
+    pub randomizer: RandomizerType,
+    pub seed: Option<u64>,
+    pub preview_length: usize,
+    pub lock_delay: f64,
+    pub lock_reset: LockReset,
+    /// How long, in seconds, a sideways move is held before it repeats
+    pub das: f64,
+    /// How long, in seconds, between repeated sideways moves. 0 moves
+    /// straight to the wall.
+    pub arr: f64,
+    /// How long, in seconds, repeated sideways moves are held back for
+    /// after a piece spawns
+    pub das_cut: f64,
+    /// How many times faster than gravity a piece falls while soft drop is
+    /// held. Infinity drops it to the stack at once.
+    pub soft_drop_factor: f64,
+    /// How long, in seconds, it takes for the next piece to appear after
+    /// one locks
+    pub are: f64,
+    /// How long, in seconds, completed lines are shown for before they
+    /// are removed
+    pub line_clear_delay: f64,
+    /// Whether a piece locking partly above the visible field ends the game
+    pub partial_lock_out: bool,
+    pub mode: GameModeType,
+    pub scoring_system: ScoringSystemType,
+    pub leveling_system: LevelingSystemType,
+    pub gravity: GravityType,
+    pub start_level: u32,
+}
+
+pub struct ConfigBuilder {
+    g_size: (u32, u32),
+    hidden_rows: u32,
+    g_offset: f64,
+    t_size: f64,
+    ui_color: [f32; 4],
+    shadow_enabled: bool,
+    rotation_system: RotationSystemType,
+    half_turn_kicks: HalfTurnKicks,
+    pieces: PieceSetType,
+    randomizer: RandomizerType,
+    seed: Option<u64>,
+    preview_length: usize,
+    lock_delay: f64,
+    lock_reset: LockReset,
+    das: f64,
+    arr: f64,
+    das_cut: f64,
+    soft_drop_factor: f64,
+    are: f64,
+    line_clear_delay: f64,
+    partial_lock_out: bool,
+    mode: GameModeType,
+    scoring_system: ScoringSystemType,
+    leveling_system: LevelingSystemType,
+    gravity: GravityType,
+    start_level: u32,
+}
+
+impl ConfigBuilder {
+    pub fn new() -> ConfigBuilder {
+        ConfigBuilder {
+            g_size: (10, 22),
+            hidden_rows: 2,
+            g_offset: 0.0,
+            t_size: 0.0,
+            ui_color: [1.0; 4],
+            shadow_enabled: false,
+            rotation_system: RotationSystemType::Srs,
+            half_turn_kicks: HalfTurnKicks::SrsPlus,
+            pieces: PieceSetType::Standard,
+            randomizer: RandomizerType::SevenBag,
+            seed: None,
+            preview_length: 1,
+            lock_delay: 0.5,
+            lock_reset: LockReset::Move(MOVE_RESET_LIMIT),
+            das: 10.0 / FRAME_RATE,
+            arr: 2.0 / FRAME_RATE,
+            das_cut: 0.0,
+            soft_drop_factor: 20.0,
+            are: 0.0,
+            line_clear_delay: 0.0,
+            partial_lock_out: false,
+            mode: GameModeType::Marathon,
+            scoring_system: ScoringSystemType::Default,
+            leveling_system: LevelingSystemType::Default,
+            gravity: GravityType::Guideline,
+            start_level: 1,
+        }
+    }
+
+    pub fn grid_size(mut self, grid_size: (u32, u32)) -> Self {
+        self.g_size = grid_size;
+        self
+    }
+
+    pub fn hidden_rows(mut self, hidden_rows: u32) -> Self {
+        self.hidden_rows = hidden_rows;
+        self
+    }
+
+    pub fn grid_offset(mut self, grid_offset: f64) -> Self {
+        self.g_offset = grid_offset;
+        self
+    }
+
+    pub fn tile_size(mut self, tile_size: f64) -> Self {
+        self.t_size = tile_size;
+        self
+    }
+
+    pub fn ui_color(mut self, ui_color: [f32; 4]) -> Self {
+        self.ui_color = ui_color;
+        self
+    }
+
+    pub fn shadow(mut self, shadow: bool) -> Self {
+        self.shadow_enabled = shadow;
+        self
+    }
+
+    pub fn rotation_system(mut self, rotation_system: RotationSystemType) -> Self {
+        self.rotation_system = rotation_system;
+        self
+    }
+
+    /// Sets the kicks tried when a piece is turned 180 degrees
+    pub fn half_turn_kicks(mut self, half_turn_kicks: HalfTurnKicks) -> Self {
+        self.half_turn_kicks = half_turn_kicks;
+        self
+    }
+
+    pub fn pieces(mut self, pieces: PieceSetType) -> Self {
+        self.pieces = pieces;
+        self
+    }
+
+    pub fn randomizer(mut self, randomizer: RandomizerType) -> Self {
+        self.randomizer = randomizer;
+        self
+    }
+
+    /// Fixes the seed used to generate pieces. Without one every game picks
+    /// its own random seed.
+    pub fn seed<S>(mut self, seed: S) -> Self
+        where S: Into<Option<u64>>
+    {
+        self.seed = seed.into();
+        self
+    }
+
+    /// Sets how many upcoming pieces are shown, up to a maximum of 6
+    pub fn preview_length(mut self, preview_length: usize) -> Self {
+        self.preview_length = cmp::min(preview_length, MAX_PREVIEW_LENGTH);
+        self
+    }
+
+    /// Sets how long, in seconds, a piece can rest on the stack before it
+    /// locks
+    pub fn lock_delay(mut self, lock_delay: f64) -> Self {
+        self.lock_delay = lock_delay;
+        self
+    }
+
+    /// Sets what is allowed to restart the lock delay
+    pub fn lock_reset(mut self, lock_reset: LockReset) -> Self {
+        self.lock_reset = lock_reset;
+        self
+    }
+
+    /// Sets how long, in seconds, a sideways move is held before it
+    /// repeats
+    pub fn das(mut self, das: f64) -> Self {
+        self.das = das;
+        self
+    }
+
+    /// Sets how long, in seconds, it is between repeated sideways moves. 0
+    /// moves pieces straight to the wall.
+    pub fn arr(mut self, arr: f64) -> Self {
+        self.arr = arr;
+        self
+    }
+
+    /// Sets how long, in seconds, repeated sideways moves are held back for
+    /// after a piece spawns
+    pub fn das_cut(mut self, das_cut: f64) -> Self {
+        self.das_cut = das_cut;
+        self
+    }
+
+    /// Sets how many times faster than gravity a piece falls while soft
+    /// drop is held. `f64::INFINITY` gives an instant "sonic" soft drop.
+    pub fn soft_drop_factor(mut self, soft_drop_factor: f64) -> Self {
+        self.soft_drop_factor = soft_drop_factor;
+        self
+    }
+
+    /// Sets how long, in seconds, it takes for the next piece to appear
+    /// after one locks (the entry delay, or ARE). Rotations and holds asked
+    /// for in the meantime are applied to the next piece as it spawns.
+    pub fn are(mut self, are: f64) -> Self {
+        self.are = are;
+        self
+    }
+
+    /// Sets how long, in seconds, completed lines flash before they are
+    /// removed. This is on top of the entry delay.
+    pub fn line_clear_delay(mut self, line_clear_delay: f64) -> Self {
+        self.line_clear_delay = line_clear_delay;
+        self
+    }
+
+    /// Sets whether a piece that locks with any of its blocks above the
+    /// visible field ends the game, rather than only one that locks
+    /// entirely above it
+    pub fn partial_lock_out(mut self, partial_lock_out: bool) -> Self {
+        self.partial_lock_out = partial_lock_out;
+        self
+    }
+
+    /// Sets the goal of the game. Some modes also pick the scoring or
+    /// leveling system, overriding the ones set here.
+    pub fn mode(mut self, mode: GameModeType) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn scoring_system(mut self, scoring_system: ScoringSystemType) -> Self {
+        self.scoring_system = scoring_system;
+        self
+    }
+
+    pub fn leveling_system(mut self, leveling_system: LevelingSystemType) -> Self {
+        self.leveling_system = leveling_system;
+        self
+    }
+
+    pub fn gravity(mut self, gravity: GravityType) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    pub fn start_level(mut self, start_level: u32) -> Self {
+        self.start_level = start_level;
+        self
+    }
+
+    /// Sets up the rules of one of the classic games, or of Master mode.
+    /// Anything set after this overrides the preset.
+    pub fn preset(self, preset: Preset) -> Self {
+        match preset {
+            Preset::Nes => {
+                self.rotation_system(RotationSystemType::Nes)
+                    .randomizer(RandomizerType::Nes)
+                    .scoring_system(ScoringSystemType::Nes)
+                    .leveling_system(LevelingSystemType::Nes)
+                    .gravity(GravityType::Nes)
+                    .das(16.0 / FRAME_RATE)
+                    .arr(6.0 / FRAME_RATE)
+                    .are(10.0 / FRAME_RATE)
+                    .line_clear_delay(18.0 / FRAME_RATE)
+                    .start_level(0)
+            }
+            Preset::GameBoy => {
+                self.rotation_system(RotationSystemType::GameBoy)
+                    .randomizer(RandomizerType::Memoryless)
+                    .scoring_system(ScoringSystemType::Nes)
+                    .leveling_system(LevelingSystemType::GameBoy)
+                    .gravity(GravityType::GameBoy)
+                    .das(24.0 / FRAME_RATE)
+                    .arr(9.0 / FRAME_RATE)
+                    .start_level(0)
+            }
+            Preset::Master => {
+                self.rotation_system(RotationSystemType::Ars)
+                    .randomizer(RandomizerType::Tgm)
+                    .leveling_system(LevelingSystemType::Tgm)
+                    .gravity(GravityType::Tgm)
+                    .das(16.0 / FRAME_RATE)
+                    .arr(1.0 / FRAME_RATE)
+                    .are(30.0 / FRAME_RATE)
+                    .line_clear_delay(41.0 / FRAME_RATE)
+                    .start_level(0)
+                    .lock_delay(0.5)
+                    .lock_reset(LockReset::Step)
+            }
+        }
+    }
+
+    /// Builds the config. Grids narrower than `MIN_GRID_WIDTH` or with fewer
+    /// than `MIN_VISIBLE_ROWS` below the hidden rows are grown to that size.
+    pub fn build(self) -> Config {
+        let width = cmp::max(self.g_size.0, MIN_GRID_WIDTH);
+        let height = cmp::max(self.g_size.1, self.hidden_rows + MIN_VISIBLE_ROWS);
+
+        Config {
+            grid_size: (width, height),
+            hidden_rows: self.hidden_rows,
+            grid_offset: self.g_offset,
+            tile_size: self.t_size,
+            ui_color: self.ui_color,
+            shadow_enabled: self.shadow_enabled,
+            rotation_system: self.rotation_system,
+            half_turn_kicks: self.half_turn_kicks,
+            pieces: self.pieces,
+            randomizer: self.randomizer,
+            seed: self.seed,
+            preview_length: self.preview_length,
+            lock_delay: self.lock_delay,
+            lock_reset: self.lock_reset,
+            das: self.das,
+            arr: self.arr,
+            das_cut: self.das_cut,
+            soft_drop_factor: self.soft_drop_factor,
+            are: self.are,
+            line_clear_delay: self.line_clear_delay,
+            partial_lock_out: self.partial_lock_out,
+            mode: self.mode,
+            scoring_system: self.scoring_system,
+            leveling_system: self.leveling_system,
+            gravity: self.gravity,
+            start_level: self.start_level,
+        }
+    }
+}
+
+/// The sets of rules that can be set up in one go
+#[derive(Copy, Clone, PartialEq)]
+pub enum Preset {
+    Nes,
+    GameBoy,
+    /// Tetris: The Grand Master style play, speeding up to 20G
+    Master,
+}
+
+impl FromStr for Preset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Preset, String> {
+        match &s.to_lowercase()[..] {
+            "nes" => Ok(Preset::Nes),
+            "gameboy" | "gb" => Ok(Preset::GameBoy),
+            "master" => Ok(Preset::Master),
+            _ => Err(format!("Unknown preset: {}", s)),
+        }
+    }
+}
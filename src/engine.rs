@@ -0,0 +1,1300 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use rand::{thread_rng, Rng};
+
+use auto_shift::AutoShift;
+use config::Config;
+use factory::TetrominoFactory;
+use gravity::{GravityCurve, FRAME_RATE, MAX_GRAVITY};
+use grid::Grid;
+use lock_delay::LockDelay;
+use mode::{GameMode, GameModeType, GameStats};
+use piece_set::PieceSet;
+use rotation::RotationSystem;
+use spin::{self, TSpin};
+use tetromino::{Direction, MoveResult, Rotation, RotationResult, Tetromino};
+
+use leveling::{Level, LevelingSystem, LevelMetaData};
+use scoring::{Score, ScoringSystem, ScoreMetaData};
+
+/// The actions a player (or anything else driving the engine) can
+/// apply to the active Tetromino
+#[derive(Copy, Clone, PartialEq)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    SoftDrop,
+    HardDrop,
+    /// Drops the piece as far as it can go without locking it
+    FirmDrop,
+    Rotate(Rotation),
+    Hold,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum EngineState {
+    Playing,
+    /// The game was lost by topping out
+    GameOver,
+    /// The goal of the game mode was reached
+    Complete,
+}
+
+/// Why a game was lost
+#[derive(Copy, Clone, PartialEq)]
+pub enum TopOut {
+    /// A new piece appeared overlapping the stack
+    BlockOut,
+    /// A piece locked entirely above the visible field
+    LockOut,
+    /// A piece locked partly above the visible field, once any lines it
+    /// completed were removed. Only checked if the config turns it on.
+    PartialLockOut,
+}
+
+/// Engine holds the complete state of a single game and knows nothing
+/// about how (or whether) it is rendered. It only advances when it is
+/// ticked or has an action applied to it.
+pub struct Engine {
+    time: Rc<Cell<f64>>,
+    config: Config,
+    seed: u64,
+    state: EngineState,
+    top_out: Option<TopOut>,
+    mode: Box<GameMode>,
+    grid: Grid,
+    lock_delay: LockDelay,
+    auto_shift: AutoShift,
+    gravity_curve: Box<GravityCurve>,
+    fall_progress: f64,
+    scoring_system: Box<ScoringSystem>,
+    score: Score,
+    leveling_system: Box<LevelingSystem>,
+    level: Level,
+    total_lines_cleared: u32,
+    pieces_locked: u32,
+    tetromino: Tetromino,
+    tetromino_factory: TetrominoFactory,
+    piece_set: PieceSet,
+    rotation_system: Box<RotationSystem>,
+    held: Option<usize>,
+    hold_used: bool,
+    /// The last rotation and the kick it used, until the piece moves
+    last_rotation: Option<(Rotation, usize)>,
+    last_lock: Option<ScoreMetaData>,
+    combo: u32,
+    last_clear_difficult: bool,
+    spawn_at: Option<f64>,
+    clearing: Vec<usize>,
+    clear_at: f64,
+    buffered_rotation: Option<Rotation>,
+    buffered_hold: bool,
+    held_rotation: Option<Rotation>,
+    hold_held: bool,
+    soft_dropping: bool,
+    soft_drop_cells: u32,
+    hard_drop_cells: u32,
+}
+
+impl Engine {
+    pub fn new(config: Config) -> Engine {
+        let piece_set = config.pieces.create(&*config.rotation_system.create(), &config);
+        Self::with_piece_set(config, piece_set)
+    }
+
+    /// Creates an engine that plays with the given pieces instead of the
+    /// set chosen in the config, e.g. one loaded from a file
+    pub fn with_piece_set(config: Config, piece_set: PieceSet) -> Engine {
+        let time = RcCell!(0.0);
+        let seed = Self::choose_seed(&config);
+        let mut factory = TetrominoFactory::new(config.randomizer.create(seed, &piece_set),
+                                                config.preview_length);
+        let rotation_system = config.rotation_system.create();
+
+        let tetromino = factory.create(&config, &piece_set);
+        let mut lock_delay = LockDelay::new(config.lock_delay, config.lock_reset, time.clone());
+        lock_delay.start(tetromino.y);
+
+        let auto_shift = AutoShift::new(config.das,
+                                        config.arr,
+                                        config.das_cut,
+                                        config.grid_size.0,
+                                        time.clone());
+        let mode = config.mode.create();
+
+        let mut engine = Engine {
+            time: time.clone(),
+            seed: seed,
+            state: EngineState::Playing,
+            top_out: None,
+            grid: Grid::new(config.grid_size.0 as usize, config.grid_size.1 as usize),
+            lock_delay: lock_delay,
+            auto_shift: auto_shift,
+            gravity_curve: config.gravity.create(),
+            fall_progress: 0.0,
+            score: Score::new(),
+            level: Level::starting_at(config.start_level),
+            scoring_system: mode.scoring_system().unwrap_or(config.scoring_system).create(),
+            leveling_system: mode.leveling_system().unwrap_or(config.leveling_system).create(),
+            mode: mode,
+            total_lines_cleared: 0,
+            pieces_locked: 0,
+            tetromino: tetromino,
+            tetromino_factory: factory,
+            piece_set: piece_set,
+            rotation_system: rotation_system,
+            held: None,
+            hold_used: false,
+            last_rotation: None,
+            last_lock: None,
+            combo: 0,
+            last_clear_difficult: false,
+            spawn_at: None,
+            clearing: Vec::new(),
+            clear_at: 0.0,
+            buffered_rotation: None,
+            buffered_hold: false,
+            held_rotation: None,
+            hold_held: false,
+            soft_dropping: false,
+            soft_drop_cells: 0,
+            hard_drop_cells: 0,
+            // Everything above is built from the config before it moves in
+            config: config,
+        };
+
+        engine.apply_spawn_gravity();
+        engine
+    }
+
+    pub fn reset(&mut self) {
+        self.time = RcCell!(0.0);
+        self.state = EngineState::Playing;
+        self.top_out = None;
+        self.grid = Grid::new(self.config.grid_size.0 as usize,
+                              self.config.grid_size.1 as usize);
+        self.seed = Self::choose_seed(&self.config);
+        self.tetromino_factory =
+            TetrominoFactory::new(self.config.randomizer.create(self.seed, &self.piece_set),
+                                  self.config.preview_length);
+        self.tetromino = self.tetromino_factory.create(&self.config, &self.piece_set);
+        self.held = None;
+        self.hold_used = false;
+        self.last_rotation = None;
+        self.last_lock = None;
+        self.combo = 0;
+        self.last_clear_difficult = false;
+        self.spawn_at = None;
+        self.clearing.clear();
+        self.buffered_rotation = None;
+        self.buffered_hold = false;
+        self.held_rotation = None;
+        self.hold_held = false;
+        self.soft_dropping = false;
+        self.soft_drop_cells = 0;
+        self.hard_drop_cells = 0;
+        self.total_lines_cleared = 0;
+        self.pieces_locked = 0;
+        self.mode = self.config.mode.create();
+        self.scoring_system =
+            self.mode.scoring_system().unwrap_or(self.config.scoring_system).create();
+        self.leveling_system =
+            self.mode.leveling_system().unwrap_or(self.config.leveling_system).create();
+        self.score = Score::new();
+        self.level = Level::starting_at(self.config.start_level);
+        self.lock_delay = LockDelay::new(self.config.lock_delay,
+                                         self.config.lock_reset,
+                                         self.time.clone());
+        self.lock_delay.start(self.tetromino.y);
+        self.auto_shift = AutoShift::new(self.config.das,
+                                         self.config.arr,
+                                         self.config.das_cut,
+                                         self.config.grid_size.0,
+                                         self.time.clone());
+        self.fall_progress = 0.0;
+        self.apply_spawn_gravity();
+    }
+
+    /// Starts a new game in the given mode
+    pub fn set_mode(&mut self, mode: GameModeType) {
+        self.config.mode = mode;
+        self.reset();
+    }
+
+    /// Advances the game by `delta` seconds
+    pub fn tick(&mut self, delta: f64) -> EngineState {
+        if self.state == EngineState::Playing {
+            self.update_time(delta);
+
+            if self.spawn_at.is_some() {
+                self.update_entry();
+            } else {
+                self.apply_auto_shift();
+                self.apply_gravity(delta);
+                self.check_landed();
+            }
+
+            self.check_complete();
+        }
+
+        self.state
+    }
+
+    /// Applies a single action to the active Tetromino. Between pieces,
+    /// rotations and holds are saved up for the next piece and everything
+    /// else is ignored.
+    pub fn apply(&mut self, action: Action) {
+        if self.state != EngineState::Playing {
+            return;
+        }
+
+        if self.spawn_at.is_some() {
+            match action {
+                Action::Rotate(rotation) => self.buffered_rotation = Some(rotation),
+                Action::Hold => self.buffered_hold = true,
+                _ => (),
+            }
+            return;
+        }
+
+        match action {
+            Action::Rotate(rotation) => {
+                self.rotate(rotation);
+            }
+            Action::MoveLeft => {
+                self.shift(Direction::West);
+            }
+            Action::MoveRight => {
+                self.shift(Direction::East);
+            }
+            Action::SoftDrop => {
+                if self.move_down() {
+                    self.soft_drop_cells += 1;
+                    self.fall_progress = 0.0;
+                }
+            }
+            Action::HardDrop => {
+                self.drop_tetromino();
+                // Lock straight away, without waiting for the lock delay
+                self.handle_blocked(true);
+            }
+            Action::FirmDrop => {
+                while self.move_down() {
+                    self.soft_drop_cells += 1;
+                }
+                self.fall_progress = 0.0;
+            }
+            Action::Hold => {
+                if self.hold_tetromino() && !self.check_block_out() {
+                    self.apply_spawn_gravity();
+                }
+            }
+        }
+    }
+
+    /// Starts holding down the button for an action. The action is applied
+    /// straight away, held sideways moves are repeated by auto shift and a
+    /// held soft drop speeds up gravity until they are released.
+    pub fn press(&mut self, action: Action) {
+        if self.state != EngineState::Playing {
+            return;
+        }
+
+        match action {
+            Action::MoveLeft => self.auto_shift.press(Direction::West),
+            Action::MoveRight => self.auto_shift.press(Direction::East),
+            Action::SoftDrop => self.soft_dropping = true,
+            Action::Rotate(rotation) => self.held_rotation = Some(rotation),
+            Action::Hold => self.hold_held = true,
+            _ => (),
+        }
+
+        self.apply(action);
+    }
+
+    /// Stops holding down the button for an action. Rotations and holds
+    /// only carry over to a piece that spawns after an entry delay if they
+    /// are still held when it spawns.
+    pub fn release(&mut self, action: Action) {
+        match action {
+            Action::MoveLeft => self.auto_shift.release(Direction::West),
+            Action::MoveRight => self.auto_shift.release(Direction::East),
+            Action::SoftDrop => self.soft_dropping = false,
+            Action::Rotate(rotation) => {
+                if self.buffered_rotation == Some(rotation) {
+                    self.buffered_rotation = None;
+                }
+                if self.held_rotation == Some(rotation) {
+                    self.held_rotation = None;
+                }
+            }
+            Action::Hold => {
+                self.buffered_hold = false;
+                self.hold_held = false;
+            }
+            _ => (),
+        }
+    }
+
+    /// Lets go of every held button, e.g. when the game is paused
+    pub fn release_all(&mut self) {
+        self.auto_shift.release_all();
+        self.soft_dropping = false;
+        self.held_rotation = None;
+        self.hold_held = false;
+    }
+
+    /// The seed the pieces of the current game were generated from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn state(&self) -> EngineState {
+        self.state
+    }
+
+    /// The goal of the game
+    pub fn mode(&self) -> &GameMode {
+        &*self.mode
+    }
+
+    /// How the game is going, as the game mode sees it
+    pub fn stats(&self) -> GameStats {
+        GameStats {
+            time: self.time.get(),
+            score: self.score.score,
+            level: self.level.level,
+            lines_cleared: self.total_lines_cleared,
+            pieces_locked: self.pieces_locked,
+        }
+    }
+
+    /// Why the game was lost, once it is over
+    pub fn top_out(&self) -> Option<TopOut> {
+        self.top_out
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// The falling piece, or `None` between pieces
+    pub fn tetromino(&self) -> Option<&Tetromino> {
+        if self.spawn_at.is_some() {
+            None
+        } else {
+            Some(&self.tetromino)
+        }
+    }
+
+    /// The completed rows that are shown until the line clear delay is
+    /// over, from the top down
+    pub fn clearing_lines(&self) -> &[usize] {
+        &self.clearing
+    }
+
+    /// How far through the line clear delay the game is, from 0 to 1
+    pub fn line_clear_progress(&self) -> f64 {
+        if self.clearing.is_empty() {
+            return 1.0;
+        }
+
+        let remaining = (self.clear_at - self.time.get()) / self.config.line_clear_delay;
+        1.0 - remaining.max(0.0).min(1.0)
+    }
+
+    /// The pieces this game is played with
+    pub fn piece_set(&self) -> &PieceSet {
+        &self.piece_set
+    }
+
+    /// The upcoming pieces, soonest first, in their spawn orientation
+    pub fn preview(&self) -> Vec<Tetromino> {
+        self.tetromino_factory
+            .preview()
+            .iter()
+            .map(|&piece| self.tetromino_factory.build(piece, &self.config, &self.piece_set))
+            .collect()
+    }
+
+    /// The held piece, in its spawn orientation
+    pub fn held(&self) -> Option<Tetromino> {
+        self.held.map(|piece| self.tetromino_factory.build(piece, &self.config, &self.piece_set))
+    }
+
+    /// Whether the active Tetromino can still be swapped with the held one
+    pub fn can_hold(&self) -> bool {
+        !self.hold_used
+    }
+
+    /// Takes what the scoring system was told about the most recently
+    /// locked piece, so a frontend can announce it once
+    pub fn take_last_lock(&mut self) -> Option<ScoreMetaData> {
+        self.last_lock.take()
+    }
+
+    pub fn score(&self) -> &Score {
+        &self.score
+    }
+
+    pub fn level(&self) -> &Level {
+        &self.level
+    }
+
+    pub fn total_lines_cleared(&self) -> u32 {
+        self.total_lines_cleared
+    }
+
+    /// Uses the configured seed if there is one, otherwise picks a new one
+    fn choose_seed(config: &Config) -> u64 {
+        config.seed.unwrap_or_else(|| thread_rng().gen())
+    }
+
+    /// Increments the global time
+    fn update_time(&mut self, delta: f64) {
+        self.time.set(self.time.get() + delta);
+    }
+
+    /// Rotates the active Tetromino, if it fits after any kicks
+    fn rotate(&mut self, rotation: Rotation) -> bool {
+        let to = self.tetromino.get_rotated_position(rotation);
+        let kicks = match rotation {
+            Rotation::Half => self.config.half_turn_kicks.kicks(self.tetromino.direction()),
+            _ => self.piece_set.kicks(&self.tetromino, to, &self.grid, &*self.rotation_system),
+        };
+
+        match self.tetromino.can_rotate(rotation, &self.grid, &kicks) {
+            RotationResult::Allow { kick, offset } => {
+                self.tetromino.rotate(rotation, offset);
+                self.last_rotation = Some((rotation, kick));
+                self.lock_delay.moved();
+                true
+            }
+            RotationResult::Deny => false,
+        }
+    }
+
+    /// Moves the active Tetromino sideways, if it can
+    fn shift(&mut self, direction: Direction) -> bool {
+        match self.tetromino.can_move(direction, &self.grid) {
+            MoveResult::Allow => {
+                match direction {
+                    Direction::West => self.tetromino.move_left(),
+                    _ => self.tetromino.move_right(),
+                }
+                self.last_rotation = None;
+                self.lock_delay.moved();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Repeats a held sideways move as many times as auto shift says it is
+    /// due, stopping early at a wall
+    fn apply_auto_shift(&mut self) {
+        if let Some(direction) = self.auto_shift.direction() {
+            let shifts = self.auto_shift.update();
+            for _ in 0..shifts {
+                if !self.shift(direction) {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Moves the active Tetromino down as many rows as gravity has pulled
+    /// it since the last tick. Whole rows are dropped straight away, and
+    /// anything left over carries on to the next tick. Holding soft drop
+    /// multiplies gravity by the soft drop factor.
+    fn apply_gravity(&mut self, delta: f64) {
+        let mut gravity = self.gravity_curve.gravity(self.level.level);
+        if self.soft_dropping {
+            // An infinite factor drops the piece to the stack at once
+            if self.config.soft_drop_factor.is_infinite() {
+                self.apply(Action::FirmDrop);
+                return;
+            }
+
+            gravity *= self.config.soft_drop_factor;
+        }
+
+        self.fall_progress += gravity.min(MAX_GRAVITY) * delta * FRAME_RATE;
+
+        while self.fall_progress >= 1.0 {
+            if !self.move_down() {
+                // Resting pieces don't save up gravity for later
+                self.fall_progress = 0.0;
+                break;
+            }
+
+            if self.soft_dropping {
+                self.soft_drop_cells += 1;
+            }
+            self.fall_progress -= 1.0;
+        }
+    }
+
+    /// At 20G a new piece lands on the stack the moment it appears, rather
+    /// than being seen at the top of the grid for a tick
+    fn apply_spawn_gravity(&mut self) {
+        if self.gravity_curve.gravity(self.level.level) >= MAX_GRAVITY {
+            while self.move_down() {}
+        }
+    }
+
+    /// Moves the active Tetromino down a single row, if it can
+    fn move_down(&mut self) -> bool {
+        match self.tetromino.can_move(Direction::South, &self.grid) {
+            MoveResult::Allow => {
+                self.tetromino.drop_down();
+                self.last_rotation = None;
+                self.lock_delay.fall(self.tetromino.y);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Locks the active Tetromino once it has rested on the stack for long
+    /// enough
+    fn check_landed(&mut self) {
+        if let MoveResult::Blocked = self.tetromino.can_move(Direction::South, &self.grid) {
+            self.lock_delay.land();
+            self.handle_blocked(false);
+        }
+    }
+
+    /// Tops out if the active Tetromino has appeared on top of the stack.
+    /// Returns whether the game ended.
+    fn check_block_out(&mut self) -> bool {
+        self.tetromino.overlaps(&self.grid) && self.top_out_with(TopOut::BlockOut)
+    }
+
+    /// Whether locking the active Tetromino, which completes
+    /// `complete_lines`, tops out
+    fn lock_out(&self, complete_lines: &[usize]) -> Option<TopOut> {
+        let hidden_rows = self.config.hidden_rows as i32;
+        let mut rows = Vec::new();
+        for (y, row) in self.tetromino.blocks.iter().enumerate() {
+            if row.iter().any(|block| block.is_some()) {
+                rows.push(self.tetromino.y + y as i32);
+            }
+        }
+
+        if rows.iter().all(|&y| y < hidden_rows) {
+            return Some(TopOut::LockOut);
+        }
+
+        if self.config.partial_lock_out {
+            // Blocks in completed lines go, and the rest move down by one
+            // row for every completed line below them
+            let above_field = rows.iter().any(|&y| {
+                let complete = complete_lines.iter().any(|&line| line as i32 == y);
+                let below = complete_lines.iter().filter(|&&line| line as i32 > y).count();
+                !complete && y + (below as i32) < hidden_rows
+            });
+
+            if above_field {
+                return Some(TopOut::PartialLockOut);
+            }
+        }
+
+        None
+    }
+
+    /// Ends the game, unless the game mode can't be lost, in which case
+    /// the stack is cleared away instead. Returns whether the game ended.
+    fn top_out_with(&mut self, top_out: TopOut) -> bool {
+        if self.mode.can_top_out() {
+            self.state = EngineState::GameOver;
+            self.top_out = Some(top_out);
+            true
+        } else {
+            self.grid = Grid::new(self.config.grid_size.0 as usize,
+                                  self.config.grid_size.1 as usize);
+            self.clearing.clear();
+            false
+        }
+    }
+
+    /// Ends the game once the game mode's goal has been reached
+    fn check_complete(&mut self) {
+        if self.state == EngineState::Playing && self.mode.is_complete(&self.stats()) {
+            self.state = EngineState::Complete;
+        }
+    }
+
+    fn handle_blocked(&mut self, force: bool) {
+        // If its blocked.. first check if the lock delay has run out
+        if self.lock_delay.expired() || force {
+            // Spins have to be checked before the piece becomes part of the grid
+            let t_spin = spin::detect_t_spin(&self.tetromino, &self.grid, self.last_rotation);
+
+            let soft_drop_cells = self.soft_drop_cells;
+            let hard_drop_cells = self.hard_drop_cells;
+
+            // Store the tetromino in the grid
+            self.grid.store_tetromino(&self.tetromino);
+            let mut complete_lines = self.grid.complete_lines();
+            if let Some(top_out) = self.lock_out(&complete_lines) {
+                if self.top_out_with(top_out) {
+                    return;
+                }
+
+                // The stack, and any lines in it, has been cleared away
+                complete_lines.clear();
+            }
+
+            let lines_cleared = complete_lines.len() as u32;
+            self.pieces_locked += 1;
+            self.total_lines_cleared += lines_cleared;
+
+            // Tetrises and T-spins that clear lines are "difficult", and two
+            // of them in a row (ignoring pieces that clear nothing) are
+            // back-to-back
+            let difficult = lines_cleared >= 4 || (lines_cleared > 0 && t_spin != TSpin::None);
+            let back_to_back = difficult && self.last_clear_difficult;
+            let combo = if lines_cleared > 0 { self.combo } else { 0 };
+            let perfect_clear = lines_cleared > 0 && self.grid.is_empty_once_cleared();
+
+            if lines_cleared > 0 {
+                self.combo += 1;
+                self.last_clear_difficult = difficult;
+            } else {
+                self.combo = 0;
+            }
+
+            {
+                let mut level_metadata = LevelMetaData {
+                    level: &mut self.level,
+                    start_level: self.config.start_level,
+                    lines_cleared: lines_cleared,
+                    total_lines_cleared: self.total_lines_cleared,
+                };
+
+                let score_metadata = ScoreMetaData {
+                    level: level_metadata.level.level,
+                    lines_cleared: lines_cleared,
+                    t_spin: t_spin,
+                    combo: combo,
+                    back_to_back: back_to_back,
+                    perfect_clear: perfect_clear,
+                    soft_drop_cells: soft_drop_cells,
+                    hard_drop_cells: hard_drop_cells,
+                };
+
+                self.scoring_system.update_score(&mut self.score, score_metadata);
+                self.last_lock = Some(score_metadata);
+
+                self.leveling_system.process(&mut level_metadata);
+            }
+
+            // The last piece of a sprint doesn't need to wait for the next
+            // tick to stop the clock
+            self.check_complete();
+            if self.state != EngineState::Playing {
+                return;
+            }
+
+            // Completed lines stay on show for the line clear delay before
+            // they are removed, and the entry delay only starts after that
+            let mut delay = self.config.are;
+            if lines_cleared > 0 {
+                if self.config.line_clear_delay > 0.0 {
+                    self.clearing = complete_lines;
+                    self.clear_at = self.time.get() + self.config.line_clear_delay;
+                    delay += self.config.line_clear_delay;
+                } else {
+                    self.grid.remove_complete_lines();
+                }
+            }
+
+            // The next piece appears once the delays are over
+            if delay > 0.0 {
+                self.spawn_at = Some(self.time.get() + delay);
+            } else {
+                self.spawn_tetromino();
+            }
+        }
+    }
+
+    /// Waits out the line clear and entry delays, then spawns the next
+    /// piece
+    fn update_entry(&mut self) {
+        if !self.clearing.is_empty() && self.time.get() >= self.clear_at {
+            self.grid.remove_complete_lines();
+            self.clearing.clear();
+        }
+
+        // Held sideways moves keep charging between pieces, but the piece
+        // isn't there to be moved
+        self.auto_shift.update();
+
+        if self.spawn_at.map_or(false, |spawn_at| self.time.get() >= spawn_at) {
+            self.spawn_tetromino();
+        }
+    }
+
+    /// Drops a Tetromino straight down until it hits the lowest
+    /// possible point.
+    fn drop_tetromino(&mut self) {
+        while let MoveResult::Allow = self.tetromino.can_move(Direction::South, &self.grid) {
+            self.tetromino.drop_down();
+            self.hard_drop_cells += 1;
+            self.last_rotation = None;
+        }
+    }
+
+    /// Brings in the next piece, holding and rotating it first if those
+    /// buttons are held as it appears after an entry delay (IHS and IRS)
+    fn spawn_tetromino(&mut self) {
+        let waited = self.spawn_at.take().is_some();
+        self.tetromino = self.tetromino_factory.create(&self.config, &self.piece_set);
+        self.hold_used = false;
+        self.last_rotation = None;
+        self.soft_drop_cells = 0;
+        self.hard_drop_cells = 0;
+        self.fall_progress = 0.0;
+        self.lock_delay.start(self.tetromino.y);
+        self.auto_shift.spawned();
+
+        // After an entry delay, rotate and hold buttons that are held down
+        // act on the new piece, whether they were pressed during the delay
+        // or before it
+        if self.buffered_hold || (waited && self.hold_held) {
+            self.buffered_hold = false;
+            self.hold_tetromino();
+        }
+
+        let held_rotation = if waited { self.held_rotation } else { None };
+        if let Some(rotation) = self.buffered_rotation.take().or(held_rotation) {
+            self.rotate(rotation);
+        }
+
+        // Holding or rotating can get a piece out of the way of the stack
+        if self.check_block_out() {
+            return;
+        }
+
+        // The new piece only knows how strong gravity is once the level has
+        // been updated
+        self.apply_spawn_gravity();
+    }
+
+    /// Swaps the active Tetromino with the held one, or with the next piece
+    /// if nothing is held yet. This can only happen once per piece.
+    fn hold_tetromino(&mut self) -> bool {
+        if self.hold_used {
+            return false;
+        }
+
+        let held = self.held.take();
+        self.held = Some(self.tetromino.piece);
+        self.tetromino = match held {
+            Some(piece) => self.tetromino_factory.build(piece, &self.config, &self.piece_set),
+            None => self.tetromino_factory.create(&self.config, &self.piece_set),
+        };
+
+        self.hold_used = true;
+        self.last_rotation = None;
+        self.soft_drop_cells = 0;
+        self.hard_drop_cells = 0;
+        self.fall_progress = 0.0;
+        self.lock_delay.start(self.tetromino.y);
+        self.auto_shift.spawned();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, Engine, EngineState, TopOut};
+    use block::Block;
+    use config::ConfigBuilder;
+    use gravity::GravityType;
+    use mode::GameModeType;
+    use piece_set::PieceSet;
+    use scoring::{ScoreMetaData, ScoringSystemType};
+    use spin::TSpin;
+    use tetromino::{Direction, Rotation, TetrominoKind};
+
+    /// The pieces dealt and the grid left behind by dropping pieces across
+    /// the grid
+    fn play(seed: u64) -> (Vec<usize>, Vec<bool>) {
+        let config = ConfigBuilder::new().grid_size((10, 22)).seed(seed).are(0.0).build();
+        let mut engine = Engine::new(config);
+        let mut pieces = Vec::new();
+
+        for i in 0..20 {
+            pieces.push(engine.tetromino().unwrap().piece);
+            let shift = if i % 2 == 0 { Action::MoveLeft } else { Action::MoveRight };
+            for _ in 0..i % 5 {
+                engine.apply(shift);
+            }
+            engine.apply(Action::HardDrop);
+            engine.tick(0.0);
+        }
+
+        let cells = engine.grid()
+            .boxes
+            .iter()
+            .flat_map(|row| row.iter())
+            .map(|block| block.is_some())
+            .collect();
+        (pieces, cells)
+    }
+
+    #[test]
+    fn the_same_seed_plays_the_same_game() {
+        assert!(play(42) == play(42));
+        assert!(play(42).0 != play(43).0);
+    }
+
+    /// Hard drops 2x2 pieces into the middle of a 4 wide grid with two
+    /// hidden rows until the game ends
+    fn top_out(height: u32, partial_lock_out: bool) -> Option<TopOut> {
+        let pieces = PieceSet::parse("piece O\ncolor 1 1 0\nshape\nXX\nXX\n").unwrap();
+        let config = ConfigBuilder::new()
+            .grid_size((4, height))
+            .hidden_rows(2)
+            .are(0.0)
+            .line_clear_delay(0.0)
+            .partial_lock_out(partial_lock_out)
+            .build();
+        let mut engine = Engine::with_piece_set(config, pieces);
+
+        while engine.state() == EngineState::Playing {
+            engine.apply(Action::HardDrop);
+            engine.tick(0.0);
+        }
+
+        engine.top_out()
+    }
+
+    #[test]
+    fn locking_above_the_field_is_a_lock_out() {
+        assert!(top_out(6, false) == Some(TopOut::LockOut));
+    }
+
+    #[test]
+    fn spawning_on_the_stack_is_a_block_out() {
+        assert!(top_out(7, false) == Some(TopOut::BlockOut));
+    }
+
+    #[test]
+    fn locking_partly_above_the_field_is_a_partial_lock_out_if_enabled() {
+        assert!(top_out(7, true) == Some(TopOut::PartialLockOut));
+    }
+
+    /// An engine playing the standard tetrominoes on a grid laid out as
+    /// `rows`, hidden rows included, with X for a block
+    fn engine_with_grid(rows: &[&str], scoring_system: ScoringSystemType) -> Engine {
+        let config = ConfigBuilder::new()
+            .grid_size((rows[0].len() as u32, rows.len() as u32))
+            .are(0.0)
+            .line_clear_delay(0.0)
+            .scoring_system(scoring_system)
+            .build();
+        let mut engine = Engine::with_piece_set(config, PieceSet::tetrominoes());
+        fill(&mut engine, rows);
+        engine
+    }
+
+    fn fill(engine: &mut Engine, rows: &[&str]) {
+        for (y, row) in rows.iter().enumerate() {
+            for (x, cell) in row.chars().enumerate() {
+                let block = if cell == 'X' { Some(Block::new([1.0; 4])) } else { None };
+                engine.grid.boxes[y][x] = block;
+            }
+        }
+    }
+
+    /// Replaces the active piece with one of the given kind, turned and
+    /// moved to (x, y)
+    fn place(engine: &mut Engine,
+             kind: TetrominoKind,
+             rotation: Option<Rotation>,
+             x: i32,
+             y: i32) {
+        let piece = engine.piece_set.find(kind).unwrap();
+        let mut tetromino =
+            engine.tetromino_factory.build(piece, &engine.config, &engine.piece_set);
+        if let Some(rotation) = rotation {
+            tetromino.rotate(rotation, (0, 0));
+        }
+        tetromino.x = x;
+        tetromino.y = y;
+        engine.tetromino = tetromino;
+    }
+
+    /// Hard drops the active piece and returns what it scored with
+    fn lock(engine: &mut Engine) -> ScoreMetaData {
+        engine.apply(Action::HardDrop);
+        engine.take_last_lock().unwrap()
+    }
+
+    static EMPTY: [&'static str; 7] = ["....", "....", "....", "....", "....", "....", "...."];
+
+    // The block above the well is left behind, so this isn't a perfect clear
+    static TETRIS_READY: [&'static str; 7] = ["....",
+                                              "....",
+                                              "X...",
+                                              "XXX.",
+                                              "XXX.",
+                                              "XXX.",
+                                              "XXX."];
+
+    // A T pointing down into this slot is a T-spin that clears nothing
+    static T_SPIN_SLOT: [&'static str; 7] = ["....",
+                                             "....",
+                                             "....",
+                                             "....",
+                                             "X...",
+                                             "....",
+                                             "X.X."];
+
+    fn tetris(engine: &mut Engine) -> ScoreMetaData {
+        fill(engine, &TETRIS_READY);
+        place(engine, TetrominoKind::I, Some(Rotation::Clockwise), 1, 0);
+        lock(engine)
+    }
+
+    fn single(engine: &mut Engine) -> ScoreMetaData {
+        fill(engine, &EMPTY);
+        place(engine, TetrominoKind::I, None, 0, 0);
+        lock(engine)
+    }
+
+    #[test]
+    fn clearing_pieces_in_a_row_build_a_combo() {
+        let mut engine = engine_with_grid(&EMPTY, ScoringSystemType::Default);
+        for combo in 0..3 {
+            place(&mut engine, TetrominoKind::I, None, 0, 0);
+            let lock = lock(&mut engine);
+            assert_eq!(lock.lines_cleared, 1);
+            assert_eq!(lock.combo, combo);
+        }
+    }
+
+    #[test]
+    fn a_piece_that_clears_nothing_ends_the_combo() {
+        let mut engine = engine_with_grid(&EMPTY, ScoringSystemType::Default);
+        single(&mut engine);
+        assert_eq!(single(&mut engine).combo, 1);
+
+        place(&mut engine, TetrominoKind::O, None, 0, 0);
+        let lock = lock(&mut engine);
+        assert_eq!(lock.lines_cleared, 0);
+        assert_eq!(lock.combo, 0);
+
+        assert_eq!(single(&mut engine).combo, 0);
+    }
+
+    #[test]
+    fn difficult_clears_in_a_row_are_back_to_back() {
+        let mut engine = engine_with_grid(&EMPTY, ScoringSystemType::Default);
+        let first = tetris(&mut engine);
+        assert_eq!(first.lines_cleared, 4);
+        assert!(!first.back_to_back);
+        assert!(tetris(&mut engine).back_to_back);
+
+        // Anything else that clears lines breaks the chain
+        assert!(!single(&mut engine).back_to_back);
+        assert!(!tetris(&mut engine).back_to_back);
+    }
+
+    #[test]
+    fn t_spins_that_clear_nothing_keep_back_to_back() {
+        let mut engine = engine_with_grid(&EMPTY, ScoringSystemType::Default);
+        tetris(&mut engine);
+
+        fill(&mut engine, &T_SPIN_SLOT);
+        place(&mut engine, TetrominoKind::T, Some(Rotation::Half), 0, 4);
+        engine.last_rotation = Some((Rotation::Clockwise, 0));
+        let t_spin = lock(&mut engine);
+        assert!(t_spin.t_spin == TSpin::Full);
+        assert_eq!(t_spin.lines_cleared, 0);
+        assert!(!t_spin.back_to_back);
+
+        assert!(tetris(&mut engine).back_to_back);
+    }
+
+    #[test]
+    fn default_scoring_adds_back_to_back_and_combo_bonuses() {
+        let mut engine = engine_with_grid(&EMPTY, ScoringSystemType::Default);
+        tetris(&mut engine);
+        assert_eq!(engine.score().score, 400);
+
+        // Half as much again for back-to-back, and 50 for a combo of one
+        tetris(&mut engine);
+        assert_eq!(engine.score().score, 400 + 600 + 50);
+    }
+
+    static PERFECT_CLEAR_READY: [&'static str; 7] = ["....",
+                                                     "....",
+                                                     "....",
+                                                     "XXX.",
+                                                     "XXX.",
+                                                     "XXX.",
+                                                     "XXX."];
+
+    /// Fills the well in `PERFECT_CLEAR_READY` with an I, which leaves the
+    /// grid empty
+    fn perfect_clear(scoring_system: ScoringSystemType) -> (ScoreMetaData, u32) {
+        let mut engine = engine_with_grid(&PERFECT_CLEAR_READY, scoring_system);
+        place(&mut engine, TetrominoKind::I, Some(Rotation::Clockwise), 1, 3);
+        let lock = lock(&mut engine);
+        (lock, engine.score().score)
+    }
+
+    #[test]
+    fn clearing_every_block_is_a_perfect_clear() {
+        let (lock, _) = perfect_clear(ScoringSystemType::Default);
+        assert_eq!(lock.lines_cleared, 4);
+        assert!(lock.perfect_clear);
+
+        let mut engine = engine_with_grid(&EMPTY, ScoringSystemType::Default);
+        assert!(!tetris(&mut engine).perfect_clear);
+    }
+
+    #[test]
+    fn perfect_clears_score_their_bonus() {
+        // A Tetris and the 2000 point bonus for a Tetris perfect clear
+        assert_eq!(perfect_clear(ScoringSystemType::Default).1, 400 + 2000);
+        assert_eq!(perfect_clear(ScoringSystemType::Guideline).1, 800 + 2000);
+    }
+
+    /// An engine without gravity, so only auto shift moves the piece, with
+    /// an O piece at the top of the 10 wide grid
+    fn shifting(das: f64, arr: f64, das_cut: f64) -> Engine {
+        let config = ConfigBuilder::new()
+            .das(das)
+            .arr(arr)
+            .das_cut(das_cut)
+            .are(0.0)
+            .gravity(GravityType::Constant(0.0))
+            .build();
+        let mut engine = Engine::with_piece_set(config, PieceSet::tetrominoes());
+        place(&mut engine, TetrominoKind::O, None, 4, 0);
+        engine
+    }
+
+    fn x(engine: &Engine) -> i32 {
+        engine.tetromino().unwrap().x
+    }
+
+    #[test]
+    fn held_moves_repeat_after_das_at_the_arr() {
+        let mut engine = shifting(0.25, 0.125, 0.0);
+        engine.press(Action::MoveRight);
+        assert_eq!(x(&engine), 5);
+
+        engine.tick(0.125);
+        assert_eq!(x(&engine), 5);
+        engine.tick(0.125);
+        assert_eq!(x(&engine), 6);
+        engine.tick(0.125);
+        assert_eq!(x(&engine), 7);
+
+        engine.release(Action::MoveRight);
+        engine.tick(0.5);
+        assert_eq!(x(&engine), 7);
+    }
+
+    #[test]
+    fn an_arr_of_zero_moves_straight_to_the_wall() {
+        let mut engine = shifting(0.25, 0.0, 0.0);
+        engine.press(Action::MoveRight);
+        engine.tick(0.25);
+        assert_eq!(x(&engine), 8);
+
+        // However small the ARR, the shift stops at the wall
+        let mut engine = shifting(0.25, 1e-300, 0.0);
+        engine.press(Action::MoveLeft);
+        engine.tick(0.25);
+        engine.tick(1.0);
+        assert_eq!(x(&engine), 0);
+    }
+
+    #[test]
+    fn das_stays_charged_for_the_next_piece() {
+        let mut engine = shifting(0.25, 0.125, 0.0);
+        engine.press(Action::MoveLeft);
+        engine.tick(0.25);
+        engine.apply(Action::HardDrop);
+
+        // The new piece moves at the next repeat instead of waiting for DAS
+        let spawned_at = x(&engine);
+        engine.tick(0.125);
+        assert_eq!(x(&engine), spawned_at - 1);
+    }
+
+    #[test]
+    fn das_cut_holds_back_the_next_piece() {
+        let mut engine = shifting(0.25, 0.125, 0.25);
+        engine.press(Action::MoveLeft);
+        engine.tick(0.25);
+        engine.apply(Action::HardDrop);
+
+        let spawned_at = x(&engine);
+        engine.tick(0.125);
+        assert_eq!(x(&engine), spawned_at);
+        engine.tick(0.125);
+        assert_eq!(x(&engine), spawned_at - 1);
+    }
+
+    /// An engine without gravity and with a quarter second entry delay
+    fn entry_delay() -> Engine {
+        let config = ConfigBuilder::new()
+            .seed(1)
+            .are(0.25)
+            .gravity(GravityType::Constant(0.0))
+            .build();
+        Engine::with_piece_set(config, PieceSet::tetrominoes())
+    }
+
+    fn direction(engine: &Engine) -> Direction {
+        engine.tetromino().unwrap().direction()
+    }
+
+    #[test]
+    fn rotate_held_since_before_the_lock_turns_the_next_piece() {
+        let mut engine = entry_delay();
+        engine.press(Action::Rotate(Rotation::Clockwise));
+        engine.apply(Action::HardDrop);
+        engine.tick(0.125);
+        assert!(engine.tetromino().is_none());
+
+        engine.tick(0.125);
+        assert!(direction(&engine) == Direction::East);
+    }
+
+    #[test]
+    fn rotate_pressed_during_the_entry_delay_turns_the_next_piece() {
+        let mut engine = entry_delay();
+        engine.apply(Action::HardDrop);
+        engine.tick(0.125);
+        engine.press(Action::Rotate(Rotation::CounterClockwise));
+        engine.tick(0.125);
+        assert!(direction(&engine) == Direction::West);
+    }
+
+    #[test]
+    fn rotate_released_before_the_spawn_does_nothing() {
+        let mut engine = entry_delay();
+        engine.press(Action::Rotate(Rotation::Clockwise));
+        engine.apply(Action::HardDrop);
+        engine.tick(0.125);
+        engine.release(Action::Rotate(Rotation::Clockwise));
+        engine.tick(0.125);
+        assert!(direction(&engine) == Direction::North);
+
+        engine.apply(Action::HardDrop);
+        engine.tick(0.125);
+        engine.press(Action::Rotate(Rotation::Clockwise));
+        engine.release(Action::Rotate(Rotation::Clockwise));
+        engine.tick(0.125);
+        assert!(direction(&engine) == Direction::North);
+    }
+
+    #[test]
+    fn hold_held_since_before_the_lock_holds_the_next_piece() {
+        let mut engine = entry_delay();
+        let first = engine.tetromino().unwrap().piece;
+        engine.press(Action::Hold);
+        engine.apply(Action::HardDrop);
+
+        let next = engine.preview()[0].piece;
+        assert!(next != first);
+        engine.tick(0.25);
+
+        // The piece that would have spawned is swapped for the held one
+        assert_eq!(engine.tetromino().unwrap().piece, first);
+        assert_eq!(engine.held().unwrap().piece, next);
+    }
+
+    #[test]
+    fn hold_pressed_during_the_entry_delay_holds_the_next_piece() {
+        let mut engine = entry_delay();
+        let first = engine.tetromino().unwrap().piece;
+        engine.apply(Action::HardDrop);
+
+        let next = engine.preview()[0].piece;
+        assert!(next != first);
+        engine.tick(0.125);
+        engine.press(Action::Hold);
+        engine.tick(0.125);
+
+        assert_eq!(engine.held().unwrap().piece, next);
+        assert!(!engine.can_hold());
+    }
+
+    fn row_is_full(engine: &Engine, y: usize) -> bool {
+        engine.grid.boxes[y].iter().all(|cell| cell.is_some())
+    }
+
+    #[test]
+    fn lines_stay_until_the_line_clear_delay_is_over() {
+        let mut engine = engine_with_grid(&EMPTY, ScoringSystemType::Default);
+        engine.config.line_clear_delay = 0.25;
+        engine.config.are = 0.125;
+        tetris(&mut engine);
+        assert_eq!(engine.clearing_lines(), &[3, 4, 5, 6]);
+
+        engine.tick(0.125);
+        assert!(row_is_full(&engine, 6));
+        assert_eq!(engine.line_clear_progress(), 0.5);
+
+        engine.tick(0.125);
+        assert!(engine.clearing_lines().is_empty());
+        assert!(!row_is_full(&engine, 6));
+        assert!(engine.grid.boxes[6][0].is_some());
+    }
+
+    #[test]
+    fn the_entry_delay_starts_after_the_line_clear_delay() {
+        let mut engine = engine_with_grid(&EMPTY, ScoringSystemType::Default);
+        engine.config.line_clear_delay = 0.25;
+        engine.config.are = 0.125;
+        tetris(&mut engine);
+
+        engine.tick(0.25);
+        assert!(engine.tetromino().is_none());
+        engine.tick(0.0625);
+        assert!(engine.tetromino().is_none());
+
+        engine.tick(0.0625);
+        assert!(engine.tetromino().is_some());
+    }
+
+    #[test]
+    fn topping_out_in_zen_clears_the_grid() {
+        let pieces = PieceSet::parse("piece O\ncolor 1 1 0\nshape\nXX\nXX\n").unwrap();
+        let config = ConfigBuilder::new()
+            .grid_size((4, 6))
+            .hidden_rows(2)
+            .are(0.0)
+            .line_clear_delay(0.0)
+            .mode(GameModeType::Zen)
+            .build();
+        let mut engine = Engine::with_piece_set(config, pieces);
+
+        // The third piece locks above the field, and the fourth lands on an
+        // empty grid
+        for _ in 0..4 {
+            engine.apply(Action::HardDrop);
+            engine.tick(0.0);
+        }
+
+        assert!(engine.state() == EngineState::Playing);
+        assert!(engine.top_out().is_none());
+        assert_eq!(engine.stats().pieces_locked, 4);
+        assert!(engine.grid.boxes[3].iter().all(|cell| cell.is_none()));
+    }
+}
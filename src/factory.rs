@@ -0,0 +1,82 @@
+use std::collections::VecDeque;
+
+use block::Block;
+use config::Config;
+use piece_set::{PieceCells, PieceSet};
+use randomizer::Randomizer;
+use tetromino::*;
+
+/// Generates the pieces handed out by the `Engine`, in the order chosen
+/// by its `Randomizer`. The factory always knows the next
+/// `preview_length` pieces ahead of time.
+pub struct TetrominoFactory {
+    randomizer: Box<Randomizer>,
+    queue: VecDeque<usize>,
+}
+
+impl TetrominoFactory {
+    pub fn new(mut randomizer: Box<Randomizer>, preview_length: usize) -> TetrominoFactory {
+        let queue = (0..preview_length).map(|_| randomizer.next()).collect();
+
+        TetrominoFactory {
+            randomizer: randomizer,
+            queue: queue,
+        }
+    }
+
+    pub fn create(&mut self, config: &Config, piece_set: &PieceSet) -> Tetromino {
+        // Keep the queue topped up, then hand out the piece at the front
+        self.queue.push_back(self.randomizer.next());
+        let piece = self.queue.pop_front().unwrap();
+
+        self.build(piece, config, piece_set)
+    }
+
+    /// The pieces that will be created next, soonest first, as indices in
+    /// the piece set
+    pub fn preview(&self) -> &VecDeque<usize> {
+        &self.queue
+    }
+
+    /// Builds the piece with the given index in the piece set, in its spawn
+    /// position
+    pub fn build(&self, piece: usize, config: &Config, piece_set: &PieceSet) -> Tetromino {
+        let definition = piece_set.get(piece);
+
+        let shape = Self::create_shape(&definition.states, definition.color, 1.0);
+        let shadow = Self::create_shape(&definition.states, definition.color, 0.65);
+
+        Tetromino::new(piece,
+                       definition.kind,
+                       shape,
+                       shadow,
+                       definition.spawn_position(config),
+                       definition.spawn_direction)
+    }
+
+    fn create_shape(states: &[PieceCells], color: [f32; 3], opacity: f32) -> TetrominoShape {
+        let block = Some(Block::new(Self::create_blended_color(color, opacity)));
+
+        let fill = |cells: &PieceCells| {
+            cells.iter()
+                .map(|row| row.iter().map(|&cell| if cell { block } else { None }).collect())
+                .collect()
+        };
+
+        TetrominoShape(fill(&states[0]), fill(&states[1]), fill(&states[2]), fill(&states[3]))
+    }
+
+    fn create_blended_color(color: [f32; 3], opacity: f32) -> [f32; 4] {
+        // If we're opaque, just return it
+        if opacity >= 1.0 {
+            [color[0], color[1], color[2], 1.0]
+        } else {
+            // Otherwise, blend it in to black
+            let r = color[0] * (1.0 - opacity);
+            let g = color[1] * (1.0 - opacity);
+            let b = color[2] * (1.0 - opacity);
+
+            [r, g, b, 1.0]
+        }
+    }
+}
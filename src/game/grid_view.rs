@@ -0,0 +1,172 @@
+use piston_window::*;
+use piston_window::character::CharacterCache;
+
+use tetrs::block::Block;
+use tetrs::engine::Engine;
+use tetrs::tetromino::Blocks;
+
+use game::render_options::RenderOptions;
+
+/// GridView draws the grid of an Engine, along with its active
+/// Tetromino and (optionally) that Tetromino's shadow
+pub struct GridView {
+    border: rectangle::Rectangle,
+    block: rectangle::Rectangle,
+}
+
+impl GridView {
+    pub fn new() -> GridView {
+        GridView {
+            border: rectangle::Rectangle::new([1.0; 4]),
+            block: rectangle::Rectangle::new([0.0, 0.0, 0.0, 1.0]),
+        }
+    }
+
+    pub fn render<'a, G, C>(&self, engine: &Engine, options: &mut RenderOptions<'a, G, C>)
+        where C: CharacterCache,
+              G: Graphics<Texture = <C as CharacterCache>::Texture>
+    {
+        let grid_offset = options.config.grid_offset;
+        let tile_size = options.config.tile_size;
+        let hidden_rows = options.config.hidden_rows as usize;
+        let grid = engine.grid();
+        let visible_rows = grid.height() - hidden_rows;
+        let clearing = engine.clearing_lines();
+        let line_clear_progress = engine.line_clear_progress();
+
+        // Draw the "border" first
+        self.border.draw([(grid_offset - 2.0) as f64,
+                          (grid_offset - 2.0) as f64,
+                          (tile_size * grid.width() as f64 + 4.0) as f64,
+                          (tile_size * visible_rows as f64 + 4.0) as f64],
+                         &Default::default(),
+                         options.context.transform,
+                         options.graphics);
+
+        // Start drawing the grid below the hidden rows. The official Tetris
+        // rules state that the grid is 10x22 but the top two rows are hidden
+        for y in hidden_rows..grid.height() {
+            for x in 0..grid.width() {
+                let adjusted_y = y - hidden_rows;
+                let cell = [x as f64 * tile_size + grid_offset,
+                            adjusted_y as f64 * tile_size + grid_offset,
+                            tile_size as f64,
+                            tile_size as f64];
+                match grid.boxes[y][x] {
+                    // Completed lines fade out over the empty grid
+                    Some(ref block) if clearing.contains(&y) => {
+                        self.block.draw(cell,
+                                        &Default::default(),
+                                        options.context.transform,
+                                        options.graphics);
+                        render_block(&clearing_block(block, line_clear_progress),
+                                     x,
+                                     y,
+                                     options);
+                    }
+                    Some(ref block) => render_block(block, x, y, options),
+                    None => {
+                        self.block.draw(cell,
+                                        &Default::default(),
+                                        options.context.transform,
+                                        options.graphics);
+                    }
+                };
+            }
+        }
+
+        // There is no active Tetromino while waiting for the next piece
+        if let Some(tetromino) = engine.tetromino() {
+            // If the shadow is enabled, draw it underneath the active Tetromino
+            if options.config.shadow_enabled {
+                let (shadow_x, shadow_y) = tetromino.find_landing_xy(grid);
+                self.render_blocks(&tetromino.shadow, shadow_x, shadow_y, options);
+            }
+
+            self.render_blocks(&tetromino.blocks, tetromino.x, tetromino.y, options);
+        }
+    }
+
+    fn render_blocks<'a, G, C>(&self,
+                               blocks: &Blocks,
+                               grid_x: i32,
+                               grid_y: i32,
+                               options: &mut RenderOptions<'a, G, C>)
+        where C: CharacterCache,
+              G: Graphics<Texture = <C as CharacterCache>::Texture>
+    {
+        for (y, row) in blocks.iter().enumerate() {
+            for (x, block) in row.iter().enumerate() {
+                if let Some(ref block) = *block {
+                    let x = grid_x + x as i32;
+                    let y = grid_y + y as i32;
+
+                    // Skip anything in the hidden rows or outside of the grid
+                    if x < 0 || y < options.config.hidden_rows as i32 ||
+                       x >= options.config.grid_size.0 as i32 ||
+                       y >= options.config.grid_size.1 as i32 {
+                        continue;
+                    }
+
+                    render_block(block, x as usize, y as usize, options);
+                }
+            }
+        }
+    }
+}
+
+/// How a block in a completed line looks part way through the line clear
+/// delay. It flashes white for the first quarter, then fades away.
+fn clearing_block(block: &Block, progress: f64) -> Block {
+    let progress = progress as f32;
+    let (white, alpha) = if progress < 0.25 {
+        (progress * 4.0, 1.0)
+    } else {
+        (1.0, (1.0 - progress) / 0.75)
+    };
+
+    let mut color = block.color;
+    for channel in &mut color[..3] {
+        *channel += (1.0 - *channel) * white;
+    }
+    color[3] *= alpha;
+
+    Block::new(color)
+}
+
+/// Draws a single block at the given grid co-ordinates
+fn render_block<'a, G, C>(block: &Block,
+                          x: usize,
+                          y: usize,
+                          options: &mut RenderOptions<'a, G, C>)
+    where C: CharacterCache,
+          G: Graphics<Texture = <C as CharacterCache>::Texture>
+{
+    let grid_offset = options.config.grid_offset;
+    let tile_size = options.config.tile_size;
+    let adjusted_y = y - options.config.hidden_rows as usize;
+
+    draw_block(block,
+               [x as f64 * tile_size + grid_offset,
+                adjusted_y as f64 * tile_size + grid_offset,
+                tile_size as f64,
+                tile_size as f64],
+               options);
+}
+
+/// Draws a single block filling the given rectangle
+pub fn draw_block<'a, G, C>(block: &Block, rect: [f64; 4], options: &mut RenderOptions<'a, G, C>)
+    where C: CharacterCache,
+          G: Graphics<Texture = <C as CharacterCache>::Texture>
+{
+    rectangle::Rectangle::new(block.color)
+        .shape(rectangle::Shape::Bevel(1.0))
+        .border(rectangle::Border {
+            color: [0.0, 0.0, 0.0, 1.0],
+            radius: 1.0,
+        })
+        .draw(rect,
+              &Default::default(),
+              options.context.transform,
+              options.graphics);
+}
@@ -0,0 +1,283 @@
+use piston_window::{rectangle, Graphics, Transformed};
+use piston_window::character::CharacterCache;
+
+use tetrs::config::Config;
+use tetrs::engine::Engine;
+use tetrs::scoring::ScoreMetaData;
+use tetrs::spin::TSpin;
+use tetrs::tetromino::Blocks;
+
+use game::grid_view::draw_block;
+use game::render_options::RenderOptions;
+use game::text::Text;
+
+// How far to the right of the grid the HUD is drawn
+static HUD_MARGIN: f64 = 20.0;
+
+// Where the game mode's fields are drawn, and how far apart they are
+static MODE_FIELDS_TOP: f64 = 129.0;
+static MODE_FIELD_SPACING: f64 = 36.0;
+
+// How far down the upcoming pieces are drawn, and how far apart they are
+static PREVIEW_TOP: f64 = 210.0;
+static PREVIEW_SPACING: f64 = 40.0;
+static PREVIEW_GAP: f64 = 8.0;
+
+// Where the held piece's box is drawn
+static HOLD_TOP: f64 = 500.0;
+static HOLD_SIZE: (f64, f64) = (80.0, 50.0);
+
+// Where callouts such as "T-SPIN DOUBLE" are drawn, and how long, in
+// seconds, they stay on screen
+static CALLOUT_TOP: usize = 78;
+static CALLOUT_SPACING: usize = 13;
+static CALLOUT_TIME: f64 = 1.5;
+
+// How long, in seconds, the perfect clear banner flashes for, and how many
+// times a second it flashes
+static BANNER_TIME: f64 = 2.0;
+static BANNER_FLASH_RATE: f64 = 4.0;
+
+/// Hud draws the labelled values displayed beside the grid
+pub struct Hud {
+    score: HudField,
+    next_label: Text,
+    hold_label: Text,
+    hold_border: rectangle::Rectangle,
+    callouts: Vec<Text>,
+    callout_time: f64,
+    banner: Text,
+    banner_time: f64,
+    color: [f32; 4],
+    x: f64,
+}
+
+impl Hud {
+    pub fn new(config: &Config) -> Hud {
+        let x = config.grid_offset + config.tile_size * config.grid_size.0 as f64 + HUD_MARGIN;
+        let visible_rows = config.grid_size.1 - config.hidden_rows;
+
+        Hud {
+            score: HudField::new("Score", (x, 29.0), config),
+            next_label: Text::new("Next", 11, x as usize, 199, config.ui_color),
+            hold_label: Text::new("Hold", 11, x as usize, 489, config.ui_color),
+            hold_border: rectangle::Rectangle::new_border(config.ui_color, 1.0),
+            callouts: Vec::new(),
+            callout_time: 0.0,
+            banner: Text::new("PERFECT CLEAR",
+                              24,
+                              (config.grid_offset + config.tile_size) as usize,
+                              (config.grid_offset +
+                               config.tile_size * (visible_rows / 2) as f64) as usize,
+                              config.ui_color),
+            banner_time: 0.0,
+            color: config.ui_color,
+            x: x,
+        }
+    }
+
+    /// Shows a callout for a locked piece, if it did anything worth
+    /// calling out
+    pub fn announce(&mut self, lock: &ScoreMetaData) {
+        if lock.perfect_clear {
+            self.banner_time = BANNER_TIME;
+        }
+
+        let lines = callouts(lock);
+        if lines.is_empty() {
+            return;
+        }
+
+        self.callouts = lines.into_iter()
+            .enumerate()
+            .map(|(index, line)| {
+                let y = CALLOUT_TOP + index * CALLOUT_SPACING;
+                Text::new(line, 11, self.x as usize, y, self.color)
+            })
+            .collect();
+        self.callout_time = CALLOUT_TIME;
+    }
+
+    /// Counts down the time left on the current callout and banner
+    pub fn update(&mut self, delta: f64) {
+        self.callout_time -= delta;
+        self.banner_time -= delta;
+    }
+
+    pub fn render<'a, G, C>(&self, engine: &Engine, options: &mut RenderOptions<'a, G, C>)
+        where C: CharacterCache,
+              G: Graphics<Texture = <C as CharacterCache>::Texture>
+    {
+        self.score.render(&format!("{}", engine.score().score), options);
+
+        let fields = engine.mode().hud_fields(&engine.stats());
+        for (index, (label, value)) in fields.into_iter().enumerate() {
+            let location = (self.x, MODE_FIELDS_TOP + index as f64 * MODE_FIELD_SPACING);
+            HudField::new(label, location, options.config).render(&value, options);
+        }
+
+        if self.callout_time > 0.0 {
+            for callout in &self.callouts {
+                callout.render(options);
+            }
+        }
+
+        // The banner is hidden for every other half of a flash
+        if self.banner_time > 0.0 && (self.banner_time * BANNER_FLASH_RATE).fract() >= 0.5 {
+            self.banner.render(options);
+        }
+
+        let preview = engine.preview();
+        if !preview.is_empty() {
+            self.next_label.render(options);
+
+            let tile_size = options.config.tile_size / 2.0;
+            let area = (HOLD_SIZE.0, PREVIEW_SPACING - PREVIEW_GAP);
+            for (index, tetromino) in preview.iter().enumerate() {
+                let location = (self.x, PREVIEW_TOP + index as f64 * PREVIEW_SPACING);
+                render_piece(&tetromino.blocks, location, area, tile_size, options);
+            }
+        }
+
+        self.render_hold(engine, options);
+    }
+
+    fn render_hold<'a, G, C>(&self, engine: &Engine, options: &mut RenderOptions<'a, G, C>)
+        where C: CharacterCache,
+              G: Graphics<Texture = <C as CharacterCache>::Texture>
+    {
+        self.hold_label.render(options);
+        self.hold_border.draw([self.x, HOLD_TOP, HOLD_SIZE.0, HOLD_SIZE.1],
+                              &Default::default(),
+                              options.context.transform,
+                              options.graphics);
+
+        if let Some(tetromino) = engine.held() {
+            // Dim the held piece while it can't be swapped back in
+            let blocks = if engine.can_hold() {
+                &tetromino.blocks
+            } else {
+                &tetromino.shadow
+            };
+
+            let tile_size = options.config.tile_size / 2.0;
+            let location = (self.x + tile_size, HOLD_TOP + tile_size);
+            let area = (HOLD_SIZE.0 - tile_size, HOLD_SIZE.1 - tile_size);
+            render_piece(blocks, location, area, tile_size, options);
+        }
+    }
+}
+
+/// The lines of text announcing a locked piece, e.g. "T-SPIN DOUBLE"
+fn callouts(lock: &ScoreMetaData) -> Vec<String> {
+    let mut callouts = Vec::new();
+
+    if lock.back_to_back {
+        callouts.push("BACK-TO-BACK".to_string());
+    }
+
+    let lines = match lock.lines_cleared {
+        1 => " SINGLE",
+        2 => " DOUBLE",
+        3 => " TRIPLE",
+        _ => "",
+    };
+
+    match lock.t_spin {
+        TSpin::Full => callouts.push(format!("T-SPIN{}", lines)),
+        TSpin::Mini => callouts.push(format!("MINI T-SPIN{}", lines)),
+        TSpin::None if lock.lines_cleared >= 4 => callouts.push("TETRIS".to_string()),
+        TSpin::None => (),
+    }
+
+    if lock.combo > 0 {
+        callouts.push(format!("{} COMBO", lock.combo));
+    }
+
+    callouts
+}
+
+/// Draws a piece outside of the grid, with the top-left of its occupied
+/// cells at `location`. Pieces too big to fit in `area` at the given tile
+/// size are drawn with smaller tiles.
+fn render_piece<'a, G, C>(blocks: &Blocks,
+                          location: (f64, f64),
+                          area: (f64, f64),
+                          tile_size: f64,
+                          options: &mut RenderOptions<'a, G, C>)
+    where C: CharacterCache,
+          G: Graphics<Texture = <C as CharacterCache>::Texture>
+{
+    let mut cells = Vec::new();
+    for (y, row) in blocks.iter().enumerate() {
+        for (x, block) in row.iter().enumerate() {
+            if let Some(block) = *block {
+                cells.push((x, y, block));
+            }
+        }
+    }
+
+    // Trim the empty rows and columns off the top and left of the box
+    let min_x = cells.iter().map(|&(x, _, _)| x).min().unwrap_or(0);
+    let min_y = cells.iter().map(|&(_, y, _)| y).min().unwrap_or(0);
+    let columns = cells.iter().map(|&(x, _, _)| x + 1 - min_x).max().unwrap_or(1);
+    let rows = cells.iter().map(|&(_, y, _)| y + 1 - min_y).max().unwrap_or(1);
+
+    let tile_size = tile_size.min(area.0 / columns as f64).min(area.1 / rows as f64);
+
+    for &(x, y, ref block) in &cells {
+        draw_block(block,
+                   [location.0 + (x - min_x) as f64 * tile_size,
+                    location.1 + (y - min_y) as f64 * tile_size,
+                    tile_size,
+                    tile_size],
+                   options);
+    }
+}
+
+/// A single label with its value drawn underneath it
+struct HudField {
+    label: &'static str,
+    location: (f64, f64),
+    color: [f32; 4],
+    font_size: u32,
+}
+
+impl HudField {
+    fn new(label: &'static str, location: (f64, f64), config: &Config) -> HudField {
+        HudField {
+            label: label,
+            location: location,
+            color: config.ui_color,
+            font_size: 16,
+        }
+    }
+
+    fn render<'a, G, C>(&self, value: &str, options: &mut RenderOptions<'a, G, C>)
+        where C: CharacterCache,
+              G: Graphics<Texture = <C as CharacterCache>::Texture>
+    {
+        let label_transform = options.context
+            .transform
+            .trans(self.location.0 as f64, self.location.1 as f64);
+
+        let value_transform = options.context
+            .transform
+            .trans(self.location.0 as f64,
+                   self.location.1 + (self.font_size + (self.font_size / 3)) as f64);
+
+        ::piston_window::Text::new_color(self.color, self.font_size - (self.font_size / 3))
+            .draw(self.label,
+                  options.character_cache,
+                  &options.context.draw_state,
+                  label_transform,
+                  options.graphics);
+
+        ::piston_window::Text::new_color(self.color, self.font_size).draw(value,
+                                                                          options.character_cache,
+                                                                          &options.context
+                                                                              .draw_state,
+                                                                          value_transform,
+                                                                          options.graphics);
+    }
+}
@@ -0,0 +1,90 @@
+use piston_window::Key;
+
+use tetrs::engine::Action;
+use tetrs::tetromino::Rotation;
+
+/// KeyBindings maps keyboard keys to the engine actions they trigger.
+/// More than one key can be bound to the same action.
+pub struct KeyBindings {
+    bindings: Vec<(Key, Action)>,
+}
+
+impl KeyBindings {
+    pub fn new() -> KeyBindings {
+        let mut bindings = KeyBindings { bindings: Vec::new() };
+
+        bindings.bind(Key::Left, Action::MoveLeft);
+        bindings.bind(Key::Right, Action::MoveRight);
+        bindings.bind(Key::Down, Action::SoftDrop);
+        bindings.bind(Key::Up, Action::FirmDrop);
+        bindings.bind(Key::Space, Action::HardDrop);
+        bindings.bind(Key::Z, Action::Rotate(Rotation::CounterClockwise));
+        bindings.bind(Key::X, Action::Rotate(Rotation::Clockwise));
+        bindings.bind(Key::A, Action::Rotate(Rotation::Half));
+        bindings.bind(Key::C, Action::Hold);
+        bindings.bind(Key::LShift, Action::Hold);
+
+        bindings
+    }
+
+    /// Binds a key to an action, replacing anything it was bound to before
+    pub fn bind(&mut self, key: Key, action: Action) {
+        self.bindings.retain(|&(bound_key, _)| bound_key != key);
+        self.bindings.push((key, action));
+    }
+
+    /// Unbinds every key bound to an action
+    pub fn unbind(&mut self, action: Action) {
+        self.bindings.retain(|&(_, bound_action)| bound_action != action);
+    }
+
+    /// The action bound to a key, if there is one
+    pub fn action(&self, key: Key) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|&&(bound_key, _)| bound_key == key)
+            .map(|&(_, action)| action)
+    }
+}
+
+/// Parses the name of a key that can be bound, e.g. "c" or "lshift"
+pub fn parse_key(name: &str) -> Result<Key, String> {
+    match &name.to_lowercase()[..] {
+        "a" => Ok(Key::A),
+        "b" => Ok(Key::B),
+        "c" => Ok(Key::C),
+        "d" => Ok(Key::D),
+        "e" => Ok(Key::E),
+        "f" => Ok(Key::F),
+        "g" => Ok(Key::G),
+        "h" => Ok(Key::H),
+        "i" => Ok(Key::I),
+        "j" => Ok(Key::J),
+        "k" => Ok(Key::K),
+        "l" => Ok(Key::L),
+        "m" => Ok(Key::M),
+        "n" => Ok(Key::N),
+        "o" => Ok(Key::O),
+        "p" => Ok(Key::P),
+        "q" => Ok(Key::Q),
+        "r" => Ok(Key::R),
+        "s" => Ok(Key::S),
+        "t" => Ok(Key::T),
+        "u" => Ok(Key::U),
+        "v" => Ok(Key::V),
+        "w" => Ok(Key::W),
+        "x" => Ok(Key::X),
+        "y" => Ok(Key::Y),
+        "z" => Ok(Key::Z),
+        "space" => Ok(Key::Space),
+        "tab" => Ok(Key::Tab),
+        "enter" | "return" => Ok(Key::Return),
+        "lshift" => Ok(Key::LShift),
+        "rshift" => Ok(Key::RShift),
+        "lctrl" => Ok(Key::LCtrl),
+        "rctrl" => Ok(Key::RCtrl),
+        "lalt" => Ok(Key::LAlt),
+        "ralt" => Ok(Key::RAlt),
+        _ => Err(format!("Unknown key: {}", name)),
+    }
+}
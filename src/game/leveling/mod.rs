@@ -1,8 +0,0 @@
-
-mod default;
-mod level;
-mod leveling_system;
-
-pub use self::default::DefaultLevelingSystem;
-pub use self::level::Level;
-pub use self::leveling_system::{LevelMetaData, LevelingSystem};
\ No newline at end of file
@@ -1,9 +1,3 @@
-macro_rules! RcCell {
-    ( $val:expr ) => (
-        Rc::new(Cell::new($val))
-    )
-}
-
 macro_rules! menu {
     ( ($config:expr, $asset_factory:expr), $( $text:expr => $result:expr ),* ) => {
         {
@@ -16,4 +10,4 @@ macro_rules! menu {
             temp_menu
         }
     }
-}
\ No newline at end of file
+}
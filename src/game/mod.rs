@@ -1,25 +1,19 @@
 extern crate find_folder;
-extern crate rand;
 
 #[macro_use]
 mod macros;
 
 mod asset_factory;
-mod block;
-pub mod config;
-mod factory;
-mod grid;
-mod leveling;
+mod grid_view;
+mod hud;
+mod key_bindings;
 mod render_options;
-mod tetromino;
 mod text;
-mod timer;
 mod window;
 
 mod scenes;
-mod scoring;
 
-pub use self::config::{Config, ConfigBuilder};
+pub use self::key_bindings::{parse_key, KeyBindings};
 pub use self::window::GameWindow;
 
 pub use self::scenes::{Menu, MenuResult, GameOver, Game, Scene, SceneResult};
@@ -2,7 +2,7 @@
 use piston_window::*;
 use piston_window::character::CharacterCache;
 
-use game::config::Config;
+use tetrs::config::Config;
 
 pub struct RenderOptions<'a, G: 'a, C: 'a>
     where C: CharacterCache,
@@ -1,61 +1,51 @@
-use std::cell::Cell;
-use std::rc::Rc;
-
 use gfx_device_gl::Factory;
 use piston_window::*;
 
+use tetrs::config::Config;
+use tetrs::engine::{Engine, EngineState};
+use tetrs::mode::GameModeType;
+use tetrs::piece_set::PieceSet;
+
 use game::asset_factory::AssetFactory;
-use game::config::Config;
-use game::factory::TetrominoFactory;
-use game::grid::Grid;
+use game::grid_view::GridView;
+use game::hud::Hud;
+use game::key_bindings::KeyBindings;
 use game::render_options::RenderOptions;
-use game::scenes::{Scene, SceneResult};
-use game::tetromino::{Direction, MoveResult, Rotation, RotationResult, Tetromino};
-use game::timer::Timer;
+use game::scenes::{GameSummary, Scene, SceneResult};
 use game::window::GameWindow;
 
-use game::leveling::{Level, LevelingSystem, DefaultLevelingSystem, LevelMetaData};
-use game::scoring::{Score, ScoringSystem, ScoreMetaData, DefaultScoringSystem};
-
-static LOCK_STEP_TIME: f64 = 0.5;
-
-static DROP_TIME: f64 = 0.5;
-static DROP_FACTOR: f64 = 0.1;
-
+/// Game adapts piston events to the Engine and draws its state
 pub struct Game {
-    time: Rc<Cell<f64>>,
     config: Config,
     asset_factory: AssetFactory,
     pause: bool,
-    grid: Grid,
-    lockstep_timer: Timer,
-    drop_timer: Timer,
-    scoring_system: Box<ScoringSystem>,
-    score: Score,
-    leveling_system: Box<LevelingSystem>,
-    level: Level,
-    total_lines_cleared: u32,
-    tetromino: Tetromino,
-    tetromino_factory: TetrominoFactory,
+    engine: Engine,
+    grid_view: GridView,
+    hud: Hud,
+    key_bindings: KeyBindings,
+    held_keys: Vec<Key>,
 }
 
 impl Scene for Game {
     fn process(&mut self, e: &Event) -> SceneResult {
         match *e {
             Event::Update(update) => {
-                // Update the global game time
-                self.update_time(update.dt);
-
-                // Drop the current block if it needs dropping
-                if let SceneResult::GameOver = self.move_down(None) {
-                    return SceneResult::GameOver;
+                if self.engine.tick(update.dt) != EngineState::Playing {
+                    let stats = self.engine.stats();
+                    return SceneResult::GameOver(GameSummary {
+                        seed: self.engine.seed(),
+                        top_out: self.engine.top_out(),
+                        mode: self.engine.mode().name(),
+                        details: self.engine.mode().summary(&stats),
+                    });
                 }
 
-                // Apply the currently active tetromino into the grid
-                self.grid.apply_tetromino(&self.tetromino, &self.config);
+                self.announce();
+                self.hud.update(update.dt);
             }
             Event::Input(ref input_event) => {
                 self.handle_input(input_event);
+                self.announce();
             }
             _ => (),
         }
@@ -78,167 +68,90 @@ impl Scene for Game {
                 character_cache: self.asset_factory.font.as_mut().unwrap(),
             };
 
-            self.grid.render(&mut options, e);
-            self.score.render(&mut options);
-            self.level.render(&mut options);
+            self.grid_view.render(&self.engine, &mut options);
+            self.hud.render(&self.engine, &mut options);
         });
     }
 }
 
 impl Game {
-    pub fn new(config: Config, gfx_factory: Factory) -> Game {
-        let time = RcCell!(0.0);
-        let factory = TetrominoFactory::new();
-
-        let tetromino = factory.create(&config);
-        let score = Score::new(&config);
-        let level = Level::new(&config);
+    /// Creates a game played with the given pieces, or with the piece set
+    /// chosen in the config if there are none
+    pub fn new(config: Config,
+               piece_set: Option<PieceSet>,
+               key_bindings: KeyBindings,
+               gfx_factory: Factory)
+               -> Game {
+        let engine = match piece_set {
+            Some(piece_set) => Engine::with_piece_set(config.clone(), piece_set),
+            None => Engine::new(config.clone()),
+        };
 
         Game {
-            time: time.clone(),
+            hud: Hud::new(&config),
             config: config,
             asset_factory: AssetFactory::new(gfx_factory),
             pause: false,
-            grid: Grid::new(),
-            lockstep_timer: Timer::new(LOCK_STEP_TIME, time.clone()),
-            drop_timer: Timer::new(DROP_TIME, time.clone()),
-            score: score,
-            level: level,
-            scoring_system: Box::new(DefaultScoringSystem),
-            leveling_system: Box::new(DefaultLevelingSystem),
-            total_lines_cleared: 0,
-            tetromino: tetromino,
-            tetromino_factory: factory,
+            engine: engine,
+            grid_view: GridView::new(),
+            key_bindings: key_bindings,
+            held_keys: Vec::new(),
         }
     }
 
     pub fn reset(&mut self) {
         self.pause = false;
-        self.time = RcCell!(0.0);
-        self.grid = Grid::new();
-        self.tetromino = self.tetromino_factory.create(&self.config);
-        self.total_lines_cleared = 0;
-        self.score = Score::new(&self.config);
-        self.level = Level::new(&self.config);
-        self.lockstep_timer = Timer::new(LOCK_STEP_TIME, self.time.clone());
-        self.drop_timer = Timer::new(DROP_TIME, self.time.clone());
+        self.engine.reset();
+        self.hud = Hud::new(&self.config);
     }
 
-    pub fn unpause(&mut self) {
+    /// Starts a new game in the given mode
+    pub fn start(&mut self, mode: GameModeType) {
+        self.config.mode = mode;
         self.pause = false;
+        self.engine.set_mode(mode);
+        self.hud = Hud::new(&self.config);
     }
 
-    /// Increments the global time
-    fn update_time(&mut self, delta: f64) {
-        self.time.set(self.time.get() + delta);
+    pub fn unpause(&mut self) {
+        self.pause = false;
     }
 
-    fn move_down<O>(&mut self, force_drop: O) -> SceneResult
-        where O: Into<Option<bool>>
-    {
-        // If we can drop, check if we are ready and drop the active tetromino
-        match self.tetromino.can_move(Direction::South, &self.grid.boxes) {
-            MoveResult::Allow => {
-                if self.drop_timer.elapsed() || force_drop.into().is_some() {
-                    self.tetromino.drop_down();
-                    self.drop_timer.reset(None);
-                    self.lockstep_timer.reset(None);
-                }
+    fn handle_input(&mut self, input: &Input) {
+        match *input {
+            Input::Press(Button::Keyboard(Key::Escape)) => {
+                // Keys released while the pause menu is up are never seen
+                self.held_keys.clear();
+                self.engine.release_all();
+                self.pause = true;
             }
-            MoveResult::Blocked => {
-                if self.tetromino.y <= 0 {
-                    // We've hit the top
-                    return SceneResult::GameOver;
-                } else {
-                    self.handle_blocked(None);
+            Input::Press(Button::Keyboard(key)) => {
+                // Ignore the presses the OS repeats while a key is held,
+                // the engine repeats moves itself
+                if self.held_keys.contains(&key) {
+                    return;
                 }
-            }
-            _ => (),
-        }
-
-        SceneResult::None
-    }
+                self.held_keys.push(key);
 
-    fn handle_blocked<O>(&mut self, force: O)
-        where O: Into<Option<bool>>
-    {
-        // If its blocked.. first check if our LockStep has elapsed
-        if self.lockstep_timer.elapsed() || force.into().is_some() {
-            // Store the tetromino in the grid and create a new tetromino
-            self.new_tetromino();
-            self.lockstep_timer.stop();
-            let lines_cleared = self.grid.remove_complete_lines(&self.config);
-            self.total_lines_cleared += lines_cleared;
-
-            {
-                let mut level_metadata = LevelMetaData {
-                    level: &mut self.level,
-                    lines_cleared: lines_cleared,
-                    total_lines_cleared: self.total_lines_cleared,
-                };
-
-                self.scoring_system.update_score(&mut self.score,
-                                                 ScoreMetaData { lines_cleared: lines_cleared });
-
-                self.leveling_system.process(&mut level_metadata);
+                if let Some(action) = self.key_bindings.action(key) {
+                    self.engine.press(action);
+                }
             }
-            self.drop_timer.reset(DROP_TIME - (self.level.level as f64 * DROP_FACTOR));
-        }
-    }
+            Input::Release(Button::Keyboard(key)) => {
+                self.held_keys.retain(|&held| held != key);
 
-    /// Drops a Tetromino straight down until it hits the lowest
-    /// possible point.
-    fn drop_tetromino(&mut self) {
-        while let MoveResult::Allow = self.tetromino.can_move(Direction::South, &self.grid.boxes) {
-            self.tetromino.drop_down();
+                if let Some(action) = self.key_bindings.action(key) {
+                    self.engine.release(action);
+                }
+            }
+            _ => (),
         }
     }
 
-    fn new_tetromino(&mut self) {
-        self.grid.store_tetromino(&self.tetromino);
-        self.tetromino = self.tetromino_factory.create(&self.config);
-    }
-
-    fn handle_input(&mut self, input: &Input) {
-        if let Input::Press(ref button) = *input {
-            match *button {
-                Button::Keyboard(Key::Escape) => self.pause = true,
-                Button::Keyboard(Key::Z) => {
-                    if let RotationResult::Allow = self.tetromino
-                        .can_rotate(Rotation::CounterClockwise, &self.grid.boxes) {
-                        self.tetromino.rotate(Rotation::CounterClockwise);
-                    }
-                }
-                Button::Keyboard(Key::X) => {
-                    if let RotationResult::Allow = self.tetromino
-                        .can_rotate(Rotation::Clockwise, &self.grid.boxes) {
-                        self.tetromino.rotate(Rotation::Clockwise);
-                    }
-                }
-                Button::Keyboard(Key::Left) => {
-                    match self.tetromino.can_move(Direction::West, &self.grid.boxes) {
-                        MoveResult::Allow => self.tetromino.move_left(),
-                        MoveResult::Blocked => self.new_tetromino(),
-                        _ => (),
-                    }
-                }
-                Button::Keyboard(Key::Right) => {
-                    match self.tetromino.can_move(Direction::East, &self.grid.boxes) {
-                        MoveResult::Allow => self.tetromino.move_right(),
-                        MoveResult::Blocked => self.new_tetromino(),
-                        _ => (),
-                    }
-                }
-                Button::Keyboard(Key::Down) => {
-                    self.move_down(true);
-                }
-                Button::Keyboard(Key::Space) => {
-                    self.drop_tetromino();
-                    // Stop the lockstep timer straight away
-                    self.handle_blocked(true);
-                }
-                _ => (),
-            }
+    /// Passes anything that happened to the engine on to the HUD
+    fn announce(&mut self) {
+        if let Some(lock) = self.engine.take_last_lock() {
+            self.hud.announce(&lock);
         }
     }
 }
@@ -1,17 +1,36 @@
 use gfx_device_gl::Factory;
 use piston_window::*;
 
+use tetrs::config::Config;
+use tetrs::engine::TopOut;
+
 use game::asset_factory::AssetFactory;
-use game::config::Config;
 use game::render_options::RenderOptions;
 use game::scenes::{Scene, SceneResult};
 use game::window::GameWindow;
 
+// Where the lines describing the game are drawn
+static DETAILS_TOP: usize = 380;
+static DETAILS_SPACING: usize = 30;
+
 pub struct GameOver {
     config: Config,
     asset_factory: AssetFactory,
 
     game_over_label: ::game::text::Text,
+    top_out_label: ::game::text::Text,
+    seed_label: ::game::text::Text,
+    details: Vec<::game::text::Text>,
+}
+
+/// The details of a finished game shown on the game over screen
+pub struct GameSummary {
+    pub seed: u64,
+    pub top_out: Option<TopOut>,
+    /// The name of the game mode that was played
+    pub mode: &'static str,
+    /// How the game went, as the game mode describes it
+    pub details: Vec<String>,
 }
 
 impl Scene for GameOver {
@@ -40,6 +59,11 @@ impl Scene for GameOver {
             };
 
             self.game_over_label.render(&mut options);
+            self.top_out_label.render(&mut options);
+            self.seed_label.render(&mut options);
+            for label in &self.details {
+                label.render(&mut options);
+            }
         });
     }
 }
@@ -47,9 +71,35 @@ impl Scene for GameOver {
 impl GameOver {
     pub fn new(config: Config, gfx_factory: Factory) -> GameOver {
         GameOver {
-            config: config,
             asset_factory: AssetFactory::new(gfx_factory),
             game_over_label: ::game::text::Text::new("Game Over", 36, 100, 264, config.ui_color),
+            top_out_label: ::game::text::Text::new("", 16, 100, 300, config.ui_color),
+            seed_label: ::game::text::Text::new("", 16, 100, 330, config.ui_color),
+            details: Vec::new(),
+            config: config,
         }
     }
+
+    pub fn set_summary(&mut self, summary: GameSummary) {
+        self.game_over_label.set_text(match summary.top_out {
+            Some(_) => "Game Over".to_string(),
+            None => format!("{} Complete", summary.mode),
+        });
+        self.top_out_label.set_text(match summary.top_out {
+            Some(TopOut::BlockOut) => "Block out",
+            Some(TopOut::LockOut) => "Lock out",
+            Some(TopOut::PartialLockOut) => "Partial lock out",
+            None => "",
+        });
+        self.seed_label.set_text(format!("Seed: {}", summary.seed));
+
+        let color = self.config.ui_color;
+        self.details = summary.details
+            .into_iter()
+            .enumerate()
+            .map(|(index, line)| {
+                ::game::text::Text::new(line, 16, 100, DETAILS_TOP + index * DETAILS_SPACING, color)
+            })
+            .collect();
+    }
 }
\ No newline at end of file
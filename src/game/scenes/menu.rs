@@ -1,8 +1,10 @@
 use gfx_device_gl::Factory;
 use piston_window::*;
 
+use tetrs::config::Config;
+use tetrs::mode::GameModeType;
+
 use game::asset_factory::AssetFactory;
-use game::config::Config;
 use game::render_options::RenderOptions;
 use game::scenes::{Scene, SceneResult};
 use game::window::GameWindow;
@@ -112,7 +114,9 @@ impl MenuItem {
 pub enum MenuResult {
     None,
     MainMenu,
+    ModeMenu,
     NewGame,
+    StartMode(GameModeType),
     ResumeGame,
     Quit,
 }
\ No newline at end of file
@@ -5,6 +5,6 @@ mod menu;
 mod scene;
 
 pub use game::scenes::game::Game;
-pub use game::scenes::game_over::GameOver;
+pub use game::scenes::game_over::{GameOver, GameSummary};
 pub use game::scenes::menu::{Menu, MenuResult};
 pub use game::scenes::scene::{Scene, SceneResult};
\ No newline at end of file
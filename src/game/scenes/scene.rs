@@ -1,6 +1,8 @@
 use piston_window::*;
 
-use game::scenes::MenuResult;
+use tetrs::mode::GameModeType;
+
+use game::scenes::{GameSummary, MenuResult};
 use game::window::GameWindow;
 
 pub trait Scene {
@@ -11,10 +13,12 @@ pub trait Scene {
 pub enum SceneResult {
     None,
     MainMenu,
+    ModeMenu,
     NewGame,
+    StartMode(GameModeType),
     PauseGame,
     ResumeGame,
-    GameOver,
+    GameOver(GameSummary),
     Quit,
 }
 
@@ -22,7 +26,9 @@ impl From<MenuResult> for SceneResult {
     fn from(menu: MenuResult) -> SceneResult {
         match menu {
             MenuResult::MainMenu => SceneResult::MainMenu,
+            MenuResult::ModeMenu => SceneResult::ModeMenu,
             MenuResult::NewGame => SceneResult::NewGame,
+            MenuResult::StartMode(mode) => SceneResult::StartMode(mode),
             MenuResult::ResumeGame => SceneResult::ResumeGame,
             MenuResult::Quit => SceneResult::Quit,
             _ => SceneResult::None,
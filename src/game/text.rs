@@ -24,6 +24,12 @@ impl Text {
         }
     }
 
+    pub fn set_text<S>(&mut self, text: S)
+        where S: Into<String>
+    {
+        self.text = text.into();
+    }
+
     pub fn set_font_size(&mut self, font_size: u32) {
         self.font_size = font_size;
     }
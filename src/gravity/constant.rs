@@ -0,0 +1,10 @@
+use gravity::GravityCurve;
+
+/// The same gravity, in G, at every level
+pub struct ConstantGravity(pub f64);
+
+impl GravityCurve for ConstantGravity {
+    fn gravity(&self, _: u32) -> f64 {
+        self.0
+    }
+}
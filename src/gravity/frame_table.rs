@@ -0,0 +1,48 @@
+use std::cmp;
+
+use gravity::{GravityCurve, FRAME_RATE};
+
+/// How many frames a piece takes to fall one row in NES Tetris, by level.
+/// Every level from 29 onwards takes a single frame.
+static NES_FRAMES_PER_ROW: [u32; 30] = [48, 43, 38, 33, 28, 23, 18, 13, 8, 6, 5, 5, 5, 4, 4, 4, 3,
+                                        3, 3, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 1];
+
+/// How many frames a piece takes to fall one row in Game Boy Tetris, by
+/// level. Every level from 20 onwards takes three frames.
+static GAME_BOY_FRAMES_PER_ROW: [u32; 21] = [53, 49, 45, 41, 37, 33, 28, 22, 17, 11, 10, 9, 8,
+                                             7, 6, 6, 5, 5, 4, 4, 3];
+
+/// The gravity of the classic games, which count how many frames a piece
+/// takes to fall one row at each level. Levels past the end of the table
+/// use its last entry.
+pub struct FrameTableGravity {
+    frame_rate: f64,
+    frames_per_row: &'static [u32],
+}
+
+impl FrameTableGravity {
+    /// The NES Tetris table, running on an NTSC NES
+    pub fn nes() -> FrameTableGravity {
+        FrameTableGravity {
+            frame_rate: 60.0988,
+            frames_per_row: &NES_FRAMES_PER_ROW,
+        }
+    }
+
+    /// The Game Boy Tetris table
+    pub fn game_boy() -> FrameTableGravity {
+        FrameTableGravity {
+            frame_rate: 59.73,
+            frames_per_row: &GAME_BOY_FRAMES_PER_ROW,
+        }
+    }
+}
+
+impl GravityCurve for FrameTableGravity {
+    fn gravity(&self, level: u32) -> f64 {
+        let index = cmp::min(level as usize, self.frames_per_row.len() - 1);
+        let rows_per_second = self.frame_rate / self.frames_per_row[index] as f64;
+
+        rows_per_second / FRAME_RATE
+    }
+}
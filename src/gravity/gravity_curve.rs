@@ -0,0 +1,6 @@
+/// Decides how quickly pieces fall at each level. Gravity is measured in
+/// G, the number of rows a piece falls every 60th of a second, so `1.0 / 60.0`
+/// is a row a second and `20.0` drops a piece to the floor instantly.
+pub trait GravityCurve {
+    fn gravity(&self, level: u32) -> f64;
+}
@@ -0,0 +1,20 @@
+use std::cmp;
+
+use gravity::{GravityCurve, FRAME_RATE};
+
+/// The guideline stops speeding up here, well past 20G. Past level 115
+/// the formula's base goes negative, so it must never be used beyond it.
+static MAX_LEVEL: u32 = 20;
+
+/// The gravity of the modern Tetris Guideline games, where a row takes
+/// `(0.8 - ((level - 1) * 0.007)) ^ (level - 1)` seconds to fall
+pub struct GuidelineGravity;
+
+impl GravityCurve for GuidelineGravity {
+    fn gravity(&self, level: u32) -> f64 {
+        let level = cmp::min(level, MAX_LEVEL) as f64;
+        let seconds_per_row = (0.8 - ((level - 1.0) * 0.007)).powf(level - 1.0);
+
+        1.0 / (seconds_per_row * FRAME_RATE)
+    }
+}
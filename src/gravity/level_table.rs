@@ -0,0 +1,64 @@
+use std::rc::Rc;
+
+use gravity::GravityCurve;
+
+/// The internal gravity of Tetris: The Grand Master, as the level each
+/// speed starts at and that speed in G. The game measures gravity in
+/// 256ths of a G.
+static TGM_GRAVITY: [(u32, f64); 30] = [(0, 4.0 / 256.0),
+                                        (30, 6.0 / 256.0),
+                                        (35, 8.0 / 256.0),
+                                        (40, 10.0 / 256.0),
+                                        (50, 12.0 / 256.0),
+                                        (60, 16.0 / 256.0),
+                                        (70, 32.0 / 256.0),
+                                        (80, 48.0 / 256.0),
+                                        (90, 64.0 / 256.0),
+                                        (100, 80.0 / 256.0),
+                                        (120, 96.0 / 256.0),
+                                        (140, 112.0 / 256.0),
+                                        (160, 128.0 / 256.0),
+                                        (170, 144.0 / 256.0),
+                                        (200, 4.0 / 256.0),
+                                        (220, 32.0 / 256.0),
+                                        (230, 64.0 / 256.0),
+                                        (233, 96.0 / 256.0),
+                                        (236, 128.0 / 256.0),
+                                        (239, 160.0 / 256.0),
+                                        (243, 192.0 / 256.0),
+                                        (247, 224.0 / 256.0),
+                                        (251, 1.0),
+                                        (300, 2.0),
+                                        (330, 3.0),
+                                        (360, 4.0),
+                                        (400, 5.0),
+                                        (420, 4.0),
+                                        (450, 3.0),
+                                        (500, 20.0)];
+
+/// Gravity looked up from a table of the levels each speed starts at, in
+/// increasing order of level. This is how user defined curves are built.
+pub struct LevelTableGravity {
+    table: Rc<[(u32, f64)]>,
+}
+
+impl LevelTableGravity {
+    pub fn new(table: Rc<[(u32, f64)]>) -> LevelTableGravity {
+        LevelTableGravity { table: table }
+    }
+
+    /// The Tetris: The Grand Master table
+    pub fn tgm() -> LevelTableGravity {
+        LevelTableGravity::new(Rc::from(&TGM_GRAVITY[..]))
+    }
+}
+
+impl GravityCurve for LevelTableGravity {
+    fn gravity(&self, level: u32) -> f64 {
+        self.table
+            .iter()
+            .take_while(|&&(start, _)| start <= level)
+            .last()
+            .map_or(0.0, |&(_, gravity)| gravity)
+    }
+}
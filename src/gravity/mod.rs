@@ -0,0 +1,162 @@
+use std::rc::Rc;
+use std::str::FromStr;
+
+mod constant;
+mod frame_table;
+mod gravity_curve;
+mod guideline;
+mod level_table;
+
+pub use gravity::constant::ConstantGravity;
+pub use gravity::frame_table::FrameTableGravity;
+pub use gravity::gravity_curve::GravityCurve;
+pub use gravity::guideline::GuidelineGravity;
+pub use gravity::level_table::LevelTableGravity;
+
+/// The number of frames in a second that G is measured against
+pub const FRAME_RATE: f64 = 60.0;
+
+/// The strongest gravity there is. At 20G a piece falls the whole height
+/// of the grid in a single frame.
+pub const MAX_GRAVITY: f64 = 20.0;
+
+/// The built in gravity curves that can be selected in the `Config`
+#[derive(Clone, PartialEq)]
+pub enum GravityType {
+    Guideline,
+    Nes,
+    GameBoy,
+    Tgm,
+    /// The same gravity, in G, at every level
+    Constant(f64),
+    /// A user defined table of the levels each speed starts at, in G
+    Custom(Rc<[(u32, f64)]>),
+}
+
+impl GravityType {
+    pub fn create(&self) -> Box<GravityCurve> {
+        match *self {
+            GravityType::Guideline => Box::new(GuidelineGravity),
+            GravityType::Nes => Box::new(FrameTableGravity::nes()),
+            GravityType::GameBoy => Box::new(FrameTableGravity::game_boy()),
+            GravityType::Tgm => Box::new(LevelTableGravity::tgm()),
+            GravityType::Constant(gravity) => Box::new(ConstantGravity(gravity)),
+            GravityType::Custom(ref table) => Box::new(LevelTableGravity::new(table.clone())),
+        }
+    }
+}
+
+impl FromStr for GravityType {
+    type Err = String;
+
+    /// Parses the name of a built in curve, a constant gravity such as
+    /// `20g` or `0.5`, or a table of levels and the gravity each one
+    /// starts, such as `0:0.02,10:0.1,20:20g`
+    fn from_str(s: &str) -> Result<GravityType, String> {
+        let s = s.to_lowercase();
+        match &s[..] {
+            "guideline" => Ok(GravityType::Guideline),
+            "nes" => Ok(GravityType::Nes),
+            "gameboy" | "gb" => Ok(GravityType::GameBoy),
+            "tgm" => Ok(GravityType::Tgm),
+            _ if s.contains(':') => {
+                parse_table(&s).map(|table| GravityType::Custom(Rc::from(table)))
+            }
+            _ => {
+                parse_gravity(&s)
+                    .map(GravityType::Constant)
+                    .ok_or_else(|| format!("Unknown gravity: {}", s))
+            }
+        }
+    }
+}
+
+/// Parses a gravity in G, with or without a trailing `g`. Gravity can't be
+/// negative or infinite, as a piece would never land.
+fn parse_gravity(s: &str) -> Option<f64> {
+    let number = if s.ends_with('g') { &s[..s.len() - 1] } else { s };
+    match number.parse::<f64>() {
+        Ok(gravity) if gravity >= 0.0 && gravity.is_finite() => Some(gravity),
+        _ => None,
+    }
+}
+
+/// Parses a comma separated list of `level:gravity` pairs, which must be
+/// in increasing order of level
+fn parse_table(s: &str) -> Result<Vec<(u32, f64)>, String> {
+    let mut table: Vec<(u32, f64)> = Vec::new();
+    for entry in s.split(',') {
+        let mut parts = entry.splitn(2, ':');
+        let level = parts.next().and_then(|level| level.trim().parse().ok());
+        let gravity = parts.next().and_then(|gravity| parse_gravity(gravity.trim()));
+
+        match (level, gravity) {
+            (Some(level), Some(gravity)) => {
+                if table.last().map_or(false, |&(last, _)| last >= level) {
+                    return Err(format!("Gravity table levels must increase: {}", s));
+                }
+                table.push((level, gravity));
+            }
+            _ => return Err(format!("Invalid gravity table entry: {}", entry)),
+        }
+    }
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::GravityType;
+
+    #[test]
+    fn guideline_gravity_speeds_up_with_the_level() {
+        let curve = GravityType::Guideline.create();
+        assert_eq!(curve.gravity(1), 1.0 / 60.0);
+        assert!(curve.gravity(1) < curve.gravity(2));
+        assert!(curve.gravity(19) < curve.gravity(20));
+    }
+
+    #[test]
+    fn guideline_gravity_stops_speeding_up_after_level_20() {
+        let curve = GravityType::Guideline.create();
+        for &level in [21, 116, 200, 999].iter() {
+            assert_eq!(curve.gravity(level), curve.gravity(20));
+        }
+        assert!(curve.gravity(999).is_finite() && curve.gravity(999) > 0.0);
+    }
+
+    #[test]
+    fn parses_constants() {
+        assert!("20g".parse::<GravityType>() == Ok(GravityType::Constant(20.0)));
+        assert!("0.5".parse::<GravityType>() == Ok(GravityType::Constant(0.5)));
+        assert!("fast".parse::<GravityType>().is_err());
+    }
+
+    #[test]
+    fn rejects_negative_and_infinite_gravity() {
+        assert!("-1".parse::<GravityType>().is_err());
+        assert!("nan".parse::<GravityType>().is_err());
+        assert!("infg".parse::<GravityType>().is_err());
+        assert!("0:0.02,10:-1".parse::<GravityType>().is_err());
+    }
+
+    #[test]
+    fn parses_tables() {
+        let table = "0:0.02, 10:0.1, 20:20g".parse::<GravityType>();
+        let expected: Rc<[(u32, f64)]> = Rc::from(vec![(0, 0.02), (10, 0.1), (20, 20.0)]);
+        assert!(table == Ok(GravityType::Custom(expected)));
+
+        assert!("10:0.1,0:0.02".parse::<GravityType>().is_err());
+        assert!("0:fast".parse::<GravityType>().is_err());
+    }
+
+    #[test]
+    fn tables_use_the_speed_of_the_last_level_reached() {
+        let curve = "0:0.02,10:0.1,20:20g".parse::<GravityType>().unwrap().create();
+        assert_eq!(curve.gravity(9), 0.02);
+        assert_eq!(curve.gravity(10), 0.1);
+        assert_eq!(curve.gravity(25), 20.0);
+    }
+}
@@ -0,0 +1,112 @@
+use block::Block;
+use tetromino::Tetromino;
+
+/// The playfield. `boxes` is indexed as `boxes[y][x]`, where `y` grows
+/// downwards from the top row. The top rows (two of them, by default) are
+/// hidden spawn rows that are never drawn.
+///
+/// Only locked blocks live in the grid; the active `Tetromino` is tracked
+/// separately by the `Engine` until it is stored.
+pub struct Grid {
+    pub boxes: Vec<Vec<Option<Block>>>,
+    width: usize,
+    height: usize,
+}
+
+impl Grid {
+    /// Creates an empty grid. `height` includes the hidden rows.
+    pub fn new(width: usize, height: usize) -> Grid {
+        Grid {
+            boxes: vec![vec![None; width]; height],
+            width: width,
+            height: height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the given co-ordinates are inside the grid
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    /// Whether the given co-ordinates are outside of the grid or have a
+    /// block in them
+    pub fn is_occupied(&self, x: i32, y: i32) -> bool {
+        !self.contains(x, y) || self.boxes[y as usize][x as usize].is_some()
+    }
+
+    /// Permanently stores the Tetromino in the grid
+    pub fn store_tetromino(&mut self, tetromino: &Tetromino) {
+        for (y, row) in tetromino.blocks.iter().enumerate() {
+            for (x, block) in row.iter().enumerate() {
+                if let Some(ref block) = *block {
+                    let x = tetromino.x + x as i32;
+                    let y = tetromino.y + y as i32;
+
+                    if !self.contains(x, y) {
+                        continue;
+                    }
+
+                    self.boxes[y as usize][x as usize] = Some(*block);
+                }
+            }
+        }
+    }
+
+    /// The rows that have a block in every column, from the top down
+    pub fn complete_lines(&self) -> Vec<usize> {
+        (0..self.height)
+            .filter(|&y| self.boxes[y].iter().all(|block| block.is_some()))
+            .collect()
+    }
+
+    /// Removes complete lines from the grid
+    pub fn remove_complete_lines(&mut self) -> u32 {
+        // Pointer to where we're currently writing lines. Everything below
+        // it has already been written.
+        let mut y_mut = self.height;
+        let mut cleared_lines = 0;
+
+        for y in (0..self.height).rev() {
+            // If every column in this row has a block,
+            // we consider it "complete"
+            let complete = self.boxes[y].iter().all(|block| block.is_some());
+
+            // If this is not a complete line, copy it
+            // into the currently pointed line
+            if !complete {
+                y_mut -= 1;
+                self.boxes.swap(y_mut, y);
+            } else {
+                cleared_lines += 1;
+            }
+        }
+
+        // The lines that moved down leave empty ones behind at the top
+        for row in &mut self.boxes[..y_mut] {
+            *row = vec![None; self.width];
+        }
+
+        cleared_lines
+    }
+
+    /// Whether there are no blocks left anywhere in the grid
+    pub fn is_empty(&self) -> bool {
+        self.boxes.iter().all(|row| row.iter().all(|block| block.is_none()))
+    }
+
+    /// Whether there will be no blocks left once the complete lines have
+    /// been removed
+    pub fn is_empty_once_cleared(&self) -> bool {
+        self.boxes.iter().all(|row| {
+            row.iter().all(|block| block.is_none()) || row.iter().all(|block| block.is_some())
+        })
+    }
+}
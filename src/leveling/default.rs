@@ -1,5 +1,5 @@
 
-use game::leveling::{LevelingSystem, LevelMetaData};
+use leveling::{LevelingSystem, LevelMetaData};
 
 pub struct DefaultLevelingSystem;
 
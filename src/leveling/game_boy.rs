@@ -0,0 +1,16 @@
+use std::cmp;
+
+use leveling::{LevelingSystem, LevelMetaData};
+
+/// Levels up the way Game Boy Tetris does. The level is the number of
+/// tens of lines cleared, but never less than the level the game started on.
+pub struct GameBoyLevelingSystem;
+
+impl LevelingSystem for GameBoyLevelingSystem {
+    fn process(&self, metadata: &mut LevelMetaData) {
+        let level = cmp::max(metadata.start_level, metadata.total_lines_cleared / 10);
+        while metadata.level.level < level {
+            metadata.level.increase();
+        }
+    }
+}
@@ -0,0 +1,19 @@
+/// The current level of a game. Levels start at 1 unless the game chose
+/// another start level, and only ever increase.
+pub struct Level {
+    pub level: u32,
+}
+
+impl Level {
+    pub fn new() -> Level {
+        Level { level: 1 }
+    }
+
+    pub fn starting_at(level: u32) -> Level {
+        Level { level: level }
+    }
+
+    pub fn increase(&mut self) {
+        self.level += 1;
+    }
+}
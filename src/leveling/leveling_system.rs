@@ -0,0 +1,15 @@
+use leveling::Level;
+
+/// Decides when the level increases. It is called once for every locked
+/// piece, after the cleared lines have been added to the total.
+pub trait LevelingSystem {
+    fn process(&self, metadata: &mut LevelMetaData);
+}
+
+pub struct LevelMetaData<'a> {
+    pub level: &'a mut Level,
+    /// The level the game started on
+    pub start_level: u32,
+    pub total_lines_cleared: u32,
+    pub lines_cleared: u32,
+}
\ No newline at end of file
@@ -0,0 +1,49 @@
+use std::str::FromStr;
+
+mod default;
+mod game_boy;
+mod level;
+mod leveling_system;
+mod nes;
+mod tgm;
+
+pub use self::default::DefaultLevelingSystem;
+pub use self::game_boy::GameBoyLevelingSystem;
+pub use self::level::Level;
+pub use self::leveling_system::{LevelMetaData, LevelingSystem};
+pub use self::nes::NesLevelingSystem;
+pub use self::tgm::TgmLevelingSystem;
+
+/// The built in leveling systems that can be selected in the `Config`
+#[derive(Copy, Clone, PartialEq)]
+pub enum LevelingSystemType {
+    Default,
+    Nes,
+    GameBoy,
+    Tgm,
+}
+
+impl LevelingSystemType {
+    pub fn create(&self) -> Box<LevelingSystem> {
+        match *self {
+            LevelingSystemType::Default => Box::new(DefaultLevelingSystem),
+            LevelingSystemType::Nes => Box::new(NesLevelingSystem),
+            LevelingSystemType::GameBoy => Box::new(GameBoyLevelingSystem),
+            LevelingSystemType::Tgm => Box::new(TgmLevelingSystem),
+        }
+    }
+}
+
+impl FromStr for LevelingSystemType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<LevelingSystemType, String> {
+        match &s.to_lowercase()[..] {
+            "default" => Ok(LevelingSystemType::Default),
+            "nes" => Ok(LevelingSystemType::Nes),
+            "gameboy" | "gb" => Ok(LevelingSystemType::GameBoy),
+            "tgm" => Ok(LevelingSystemType::Tgm),
+            _ => Err(format!("Unknown leveling system: {}", s)),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use std::cmp;
+
+use leveling::{LevelingSystem, LevelMetaData};
+
+/// Levels up the way NES Tetris does. Starting on a higher level delays
+/// the first level up until enough lines have been cleared to have reached
+/// it naturally (within limits), after which every 10 lines is a level.
+pub struct NesLevelingSystem;
+
+impl LevelingSystem for NesLevelingSystem {
+    fn process(&self, metadata: &mut LevelMetaData) {
+        let start = metadata.start_level;
+        let first_level_up = cmp::min(start * 10 + 10,
+                                      cmp::max(100, (start * 10).saturating_sub(50)));
+
+        if metadata.total_lines_cleared < first_level_up {
+            return;
+        }
+
+        let level = start + 1 + (metadata.total_lines_cleared - first_level_up) / 10;
+        while metadata.level.level < level {
+            metadata.level.increase();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NesLevelingSystem;
+    use leveling::{Level, LevelingSystem, LevelMetaData};
+
+    fn level_after(start_level: u32, total_lines_cleared: u32) -> u32 {
+        let mut level = Level::starting_at(start_level);
+        NesLevelingSystem.process(&mut LevelMetaData {
+            level: &mut level,
+            start_level: start_level,
+            total_lines_cleared: total_lines_cleared,
+            lines_cleared: 1,
+        });
+        level.level
+    }
+
+    #[test]
+    fn low_levels_level_up_after_enough_lines_to_reach_them() {
+        assert_eq!(level_after(0, 9), 0);
+        assert_eq!(level_after(0, 10), 1);
+        assert_eq!(level_after(0, 25), 2);
+        assert_eq!(level_after(9, 99), 9);
+        assert_eq!(level_after(9, 100), 10);
+    }
+
+    #[test]
+    fn high_levels_level_up_after_at_most_100_lines() {
+        // Level 15 would take 160 lines, but waits only 100
+        assert_eq!(level_after(15, 99), 15);
+        assert_eq!(level_after(15, 100), 16);
+
+        // From level 16 on it is 10 * start - 50 lines
+        assert_eq!(level_after(18, 129), 18);
+        assert_eq!(level_after(18, 130), 19);
+        assert_eq!(level_after(18, 140), 20);
+    }
+}
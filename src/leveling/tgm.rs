@@ -0,0 +1,64 @@
+use leveling::{LevelingSystem, LevelMetaData};
+
+// The highest level there is
+static MAX_LEVEL: u32 = 999;
+
+/// Levels up the way Tetris: The Grand Master does. Every line cleared is
+/// worth a level, and so is every piece, except that a piece can't take
+/// the level past the last level of a section (99, 199, ... 998). Only a
+/// line clear can do that.
+pub struct TgmLevelingSystem;
+
+impl LevelingSystem for TgmLevelingSystem {
+    fn process(&self, metadata: &mut LevelMetaData) {
+        for _ in 0..metadata.lines_cleared {
+            increase(metadata);
+        }
+
+        let level = metadata.level.level;
+        let section_stop = level % 100 == 99 || level == MAX_LEVEL - 1;
+        if !section_stop {
+            increase(metadata);
+        }
+    }
+}
+
+fn increase(metadata: &mut LevelMetaData) {
+    if metadata.level.level < MAX_LEVEL {
+        metadata.level.increase();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TgmLevelingSystem;
+    use leveling::{Level, LevelingSystem, LevelMetaData};
+
+    fn level_after(level: u32, lines_cleared: u32) -> u32 {
+        let mut level = Level::starting_at(level);
+        TgmLevelingSystem.process(&mut LevelMetaData {
+            level: &mut level,
+            start_level: 0,
+            total_lines_cleared: 0,
+            lines_cleared: lines_cleared,
+        });
+        level.level
+    }
+
+    #[test]
+    fn pieces_and_lines_are_each_worth_a_level() {
+        assert_eq!(level_after(0, 0), 1);
+        assert_eq!(level_after(0, 1), 2);
+        assert_eq!(level_after(0, 4), 5);
+    }
+
+    #[test]
+    fn only_lines_pass_a_section_stop() {
+        assert_eq!(level_after(98, 0), 99);
+        assert_eq!(level_after(99, 0), 99);
+        assert_eq!(level_after(96, 3), 99);
+        assert_eq!(level_after(99, 2), 102);
+        assert_eq!(level_after(998, 0), 998);
+        assert_eq!(level_after(998, 4), 999);
+    }
+}
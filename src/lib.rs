@@ -0,0 +1,47 @@
+//! The TetRS rules engine.
+//!
+//! Everything needed to simulate a game of Tetris lives here, with no
+//! dependency on a window or a renderer. The `tetrs` binary is simply one
+//! consumer of this library; bots, analyzers and other frontends can drive
+//! an `Engine` in exactly the same way.
+
+extern crate rand;
+
+#[macro_use]
+mod macros;
+
+pub mod auto_shift;
+pub mod block;
+pub mod config;
+pub mod engine;
+pub mod factory;
+pub mod gravity;
+pub mod grid;
+pub mod leveling;
+pub mod lock_delay;
+pub mod mode;
+pub mod piece_set;
+pub mod randomizer;
+pub mod rotation;
+pub mod scoring;
+pub mod spin;
+pub mod tetromino;
+
+pub use block::Block;
+pub use config::{Config, ConfigBuilder, Preset};
+pub use engine::{Action, Engine, EngineState, TopOut};
+pub use factory::TetrominoFactory;
+pub use grid::Grid;
+pub use gravity::{GravityCurve, GravityType};
+pub use leveling::{Level, LevelingSystem, LevelingSystemType, DefaultLevelingSystem,
+                   GameBoyLevelingSystem, LevelMetaData, NesLevelingSystem, TgmLevelingSystem};
+pub use lock_delay::LockReset;
+pub use mode::{GameMode, GameModeType, GameStats};
+pub use piece_set::{Kicks, PieceCells, PieceDefinition, PieceSet, PieceSetType};
+pub use randomizer::{Randomizer, RandomizerType};
+pub use rotation::{HalfTurnKicks, RotationSystem, RotationSystemType};
+pub use scoring::{Score, ScoringSystem, ScoringSystemType, ScoreMetaData, DefaultScoringSystem,
+                  GuidelineScoringSystem, NesScoringSystem};
+pub use spin::TSpin;
+pub use tetromino::{Blocks, Direction, MoveResult, Rotation, RotationResult, Tetromino,
+                    TetrominoKind, TetrominoShape};
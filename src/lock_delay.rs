@@ -0,0 +1,187 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// The number of moves and rotations that may reset the lock delay under
+/// the guideline "move reset" rules
+pub const MOVE_RESET_LIMIT: u32 = 15;
+
+/// Decides what is allowed to restart the lock delay once a piece has
+/// landed
+#[derive(Copy, Clone, PartialEq)]
+pub enum LockReset {
+    /// Successful moves and rotations restart the delay, up to the given
+    /// number of times. The count starts over whenever the piece falls
+    /// below the lowest row it has reached. Once it runs out the piece
+    /// locks as soon as it is on the ground.
+    Move(u32),
+    /// Only falling a row restarts the delay
+    Step,
+    /// Every successful move and rotation restarts the delay
+    Infinity,
+}
+
+impl FromStr for LockReset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<LockReset, String> {
+        match &s.to_lowercase()[..] {
+            "move" => Ok(LockReset::Move(MOVE_RESET_LIMIT)),
+            "step" => Ok(LockReset::Step),
+            "infinity" | "infinite" => Ok(LockReset::Infinity),
+            _ => Err(format!("Unknown lock reset: {}", s)),
+        }
+    }
+}
+
+/// LockDelay tracks how long the active piece has been resting on the
+/// stack, and whether it is time for it to lock
+pub struct LockDelay {
+    delay: f64,
+    reset: LockReset,
+    global_time: Rc<Cell<f64>>,
+    landed_at: Option<f64>,
+    resets: u32,
+    lowest_y: i32,
+}
+
+impl LockDelay {
+    pub fn new(delay: f64, reset: LockReset, global_time: Rc<Cell<f64>>) -> LockDelay {
+        LockDelay {
+            delay: delay,
+            reset: reset,
+            global_time: global_time,
+            landed_at: None,
+            resets: 0,
+            lowest_y: 0,
+        }
+    }
+
+    /// Starts over for a newly spawned piece at row `y`
+    pub fn start(&mut self, y: i32) {
+        self.landed_at = None;
+        self.resets = 0;
+        self.lowest_y = y;
+    }
+
+    /// The piece is resting on the stack. The delay starts counting down
+    /// the first time this is called after the piece lands.
+    pub fn land(&mut self) {
+        if self.landed_at.is_none() {
+            self.landed_at = Some(self.global_time.get());
+        }
+    }
+
+    /// The piece has fallen a row and is now at row `y`
+    pub fn fall(&mut self, y: i32) {
+        self.landed_at = None;
+
+        if y > self.lowest_y {
+            self.lowest_y = y;
+            self.resets = 0;
+        }
+    }
+
+    /// The piece was successfully moved or rotated
+    pub fn moved(&mut self) {
+        if self.landed_at.is_none() {
+            return;
+        }
+
+        match self.reset {
+            LockReset::Move(limit) => {
+                if self.resets < limit {
+                    self.resets += 1;
+                    self.landed_at = Some(self.global_time.get());
+                }
+            }
+            LockReset::Infinity => self.landed_at = Some(self.global_time.get()),
+            LockReset::Step => (),
+        }
+    }
+
+    /// Whether the piece should lock now
+    pub fn expired(&self) -> bool {
+        match self.landed_at {
+            Some(landed_at) => {
+                let out_of_resets = match self.reset {
+                    LockReset::Move(limit) => self.resets >= limit,
+                    _ => false,
+                };
+
+                out_of_resets || self.global_time.get() - landed_at >= self.delay
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use super::{LockDelay, LockReset, MOVE_RESET_LIMIT};
+
+    fn landed(reset: LockReset) -> (LockDelay, Rc<Cell<f64>>) {
+        let time = Rc::new(Cell::new(0.0));
+        let mut lock_delay = LockDelay::new(0.5, reset, time.clone());
+        lock_delay.start(0);
+        lock_delay.land();
+        (lock_delay, time)
+    }
+
+    #[test]
+    fn expires_once_the_delay_has_passed() {
+        let (lock_delay, time) = landed(LockReset::Step);
+        time.set(0.4);
+        assert!(!lock_delay.expired());
+        time.set(0.5);
+        assert!(lock_delay.expired());
+    }
+
+    #[test]
+    fn move_reset_runs_out_after_the_limit() {
+        let (mut lock_delay, time) = landed(LockReset::Move(MOVE_RESET_LIMIT));
+        for i in 0..MOVE_RESET_LIMIT - 1 {
+            time.set(i as f64 * 0.4);
+            lock_delay.moved();
+            assert!(!lock_delay.expired());
+        }
+
+        lock_delay.moved();
+        assert!(lock_delay.expired());
+    }
+
+    #[test]
+    fn falling_below_the_lowest_row_restores_move_resets() {
+        let (mut lock_delay, _) = landed(LockReset::Move(2));
+        lock_delay.moved();
+        lock_delay.moved();
+        assert!(lock_delay.expired());
+
+        lock_delay.fall(1);
+        lock_delay.land();
+        lock_delay.moved();
+        assert!(!lock_delay.expired());
+    }
+
+    #[test]
+    fn step_reset_ignores_moves() {
+        let (mut lock_delay, time) = landed(LockReset::Step);
+        time.set(0.4);
+        lock_delay.moved();
+        time.set(0.5);
+        assert!(lock_delay.expired());
+    }
+
+    #[test]
+    fn infinity_reset_never_runs_out() {
+        let (mut lock_delay, time) = landed(LockReset::Infinity);
+        for i in 0..100 {
+            time.set(i as f64 * 0.4);
+            lock_delay.moved();
+            assert!(!lock_delay.expired());
+        }
+    }
+}
@@ -0,0 +1,5 @@
+macro_rules! RcCell {
+    ( $val:expr ) => (
+        Rc::new(Cell::new($val))
+    )
+}
@@ -2,29 +2,188 @@
 mod game;
 
 use std::cell::{RefCell, RefMut};
+use std::io::{self, Write};
+use std::str::FromStr;
 
 extern crate gfx_device_gl;
 extern crate piston_window;
+extern crate tetrs;
 
-use game::{Config, ConfigBuilder, Menu, MenuResult, Game, GameOver, GameWindow, Scene, SceneResult};
+use tetrs::{Action, Config, ConfigBuilder, GameModeType, PieceSet};
+use tetrs::config::{MIN_GRID_WIDTH, MIN_VISIBLE_ROWS};
+
+use game::{parse_key, KeyBindings, Menu, MenuResult, Game, GameOver, GameWindow, Scene,
+           SceneResult};
+
+static WINDOW_HEIGHT: u32 = 600;
+static GRID_OFFSET: f64 = 10.0;
+static GRID_AREA_HEIGHT: f64 = 580.0;
+static MAX_TILE_SIZE: f64 = 29.0;
+static HUD_WIDTH: f64 = 150.0;
 
 fn main() {
-    let shadow_arg = std::env::args().nth(1);
-    let shadow_enabled = shadow_arg.is_some() && shadow_arg.unwrap() == "--shadow-enabled";
-
-    let mut window = GameWindow::new(450, 600, "TetRS");
-    let config = ConfigBuilder::new()
-        .grid_size((10, 22))
-        .grid_offset(10.0)
-        .tile_size(29.0)
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let shadow_enabled = args.iter().any(|arg| arg == "--shadow-enabled");
+
+    let seed = arg_value(&args, "--seed").map(|value| {
+        value.parse().unwrap_or_else(|_| exit_with(format!("Invalid seed: {}", value)))
+    });
+
+    let preview_length = arg_value(&args, "--preview").map_or(5, |value| {
+        value.parse().unwrap_or_else(|_| exit_with(format!("Invalid preview length: {}", value)))
+    });
+
+    let width = arg_value(&args, "--width").map_or(10, |value| {
+        value.parse().unwrap_or_else(|_| exit_with(format!("Invalid width: {}", value)))
+    });
+
+    let height = arg_value(&args, "--height").map_or(20, |value| {
+        value.parse().unwrap_or_else(|_| exit_with(format!("Invalid height: {}", value)))
+    });
+
+    let hidden_rows = arg_value(&args, "--hidden-rows").map_or(2, |value| {
+        value.parse().unwrap_or_else(|_| exit_with(format!("Invalid hidden rows: {}", value)))
+    });
+
+    if width < MIN_GRID_WIDTH || height < MIN_VISIBLE_ROWS {
+        exit_with::<()>(format!("The grid must be at least {} wide and {} tall",
+                                MIN_GRID_WIDTH,
+                                MIN_VISIBLE_ROWS));
+    }
+
+    // Shrink the tiles of tall grids so that they still fit in the window
+    let tile_size = f64::min(MAX_TILE_SIZE, GRID_AREA_HEIGHT / height as f64);
+    let window_width = GRID_OFFSET + tile_size * width as f64 + HUD_WIDTH;
+
+    let mut window = GameWindow::new(window_width as u32, WINDOW_HEIGHT, "TetRS");
+    let mut builder = ConfigBuilder::new()
+        .grid_size((width, height + hidden_rows))
+        .hidden_rows(hidden_rows)
+        .grid_offset(GRID_OFFSET)
+        .tile_size(tile_size)
         .shadow(shadow_enabled)
-        .ui_color([1.0; 4])
-        .build();
+        .seed(seed)
+        .preview_length(preview_length)
+        .ui_color([1.0; 4]);
+
+    // A preset sets up several rules at once, so the flags for individual
+    // rules are applied on top of it
+    if let Some(preset) = parse_arg(&args, "--preset") {
+        builder = builder.preset(preset);
+    }
+
+    if let Some(rotation_system) = parse_arg(&args, "--rotation") {
+        builder = builder.rotation_system(rotation_system);
+    }
+
+    if let Some(half_turn_kicks) = parse_arg(&args, "--half-kicks") {
+        builder = builder.half_turn_kicks(half_turn_kicks);
+    }
+
+    if let Some(pieces) = parse_arg(&args, "--pieces") {
+        builder = builder.pieces(pieces);
+    }
+
+    if let Some(randomizer) = parse_arg(&args, "--randomizer") {
+        builder = builder.randomizer(randomizer);
+    }
+
+    if let Some(value) = arg_value(&args, "--lock-delay") {
+        builder = builder.lock_delay(value.parse().unwrap_or_else(|_| {
+            exit_with(format!("Invalid lock delay: {}", value))
+        }));
+    }
+
+    if let Some(lock_reset) = parse_arg(&args, "--lock-reset") {
+        builder = builder.lock_reset(lock_reset);
+    }
+
+    if let Some(value) = arg_value(&args, "--das") {
+        builder = builder.das(value.parse().unwrap_or_else(|_| {
+            exit_with(format!("Invalid DAS: {}", value))
+        }));
+    }
+
+    if let Some(value) = arg_value(&args, "--arr") {
+        builder = builder.arr(value.parse().unwrap_or_else(|_| {
+            exit_with(format!("Invalid ARR: {}", value))
+        }));
+    }
 
-    let main_menu = RefCell::new(create_main_menu(config, &window));
-    let pause_menu = RefCell::new(create_pause_menu(config, &window));
+    if let Some(value) = arg_value(&args, "--das-cut") {
+        builder = builder.das_cut(value.parse().unwrap_or_else(|_| {
+            exit_with(format!("Invalid DAS cut: {}", value))
+        }));
+    }
 
-    let game = RefCell::new(Game::new(config, window.piston_window.factory.clone()));
+    if let Some(value) = arg_value(&args, "--soft-drop") {
+        builder = builder.soft_drop_factor(match value {
+            "sonic" => std::f64::INFINITY,
+            _ => {
+                // A factor of zero or less would hold the piece in place
+                // while Down is held
+                match value.parse::<f64>() {
+                    Ok(factor) if factor > 0.0 && factor.is_finite() => factor,
+                    _ => exit_with(format!("Invalid soft drop factor: {}", value)),
+                }
+            }
+        });
+    }
+
+    if let Some(value) = arg_value(&args, "--are") {
+        builder = builder.are(value.parse().unwrap_or_else(|_| {
+            exit_with(format!("Invalid ARE: {}", value))
+        }));
+    }
+
+    if let Some(value) = arg_value(&args, "--line-clear-delay") {
+        builder = builder.line_clear_delay(value.parse().unwrap_or_else(|_| {
+            exit_with(format!("Invalid line clear delay: {}", value))
+        }));
+    }
+
+    if args.iter().any(|arg| arg == "--partial-lock-out") {
+        builder = builder.partial_lock_out(true);
+    }
+
+    if let Some(scoring_system) = parse_arg(&args, "--scoring") {
+        builder = builder.scoring_system(scoring_system);
+    }
+
+    if let Some(gravity) = parse_arg(&args, "--gravity") {
+        builder = builder.gravity(gravity);
+    }
+
+    if let Some(value) = arg_value(&args, "--level") {
+        builder = builder.start_level(value.parse().unwrap_or_else(|_| {
+            exit_with(format!("Invalid level: {}", value))
+        }));
+    }
+
+    let config = builder.build();
+
+    // A piece set file replaces whichever set was chosen with --pieces
+    let piece_set = arg_value(&args, "--piece-file")
+        .map(|path| PieceSet::load(path).unwrap_or_else(|e| exit_with(e)));
+
+    // The hold keys can be swapped for a comma separated list of others,
+    // e.g. `--hold-key lshift,rshift`
+    let mut key_bindings = KeyBindings::new();
+    if let Some(value) = arg_value(&args, "--hold-key") {
+        key_bindings.unbind(Action::Hold);
+        for name in value.split(',') {
+            key_bindings.bind(parse_key(name).unwrap_or_else(|e| exit_with(e)), Action::Hold);
+        }
+    }
+
+    let main_menu = RefCell::new(create_main_menu(config.clone(), &window));
+    let mode_menu = RefCell::new(create_mode_menu(config.clone(), &window));
+    let pause_menu = RefCell::new(create_pause_menu(config.clone(), &window));
+
+    let game = RefCell::new(Game::new(config.clone(),
+                                      piece_set,
+                                      key_bindings,
+                                      window.piston_window.factory.clone()));
     let gameover = RefCell::new(GameOver::new(config, window.piston_window.factory.clone()));
 
     let mut scene: RefMut<Scene> = main_menu.borrow_mut();
@@ -34,10 +193,17 @@ fn main() {
             SceneResult::MainMenu => {
                 scene = main_menu.borrow_mut();
             }
+            SceneResult::ModeMenu => {
+                scene = mode_menu.borrow_mut();
+            }
             SceneResult::NewGame => {
                 game.borrow_mut().reset();
                 scene = game.borrow_mut();
             }
+            SceneResult::StartMode(mode) => {
+                game.borrow_mut().start(mode);
+                scene = game.borrow_mut();
+            }
             SceneResult::PauseGame => {
                 scene = pause_menu.borrow_mut();
             }
@@ -45,7 +211,8 @@ fn main() {
                 game.borrow_mut().unpause();
                 scene = game.borrow_mut();
             }
-            SceneResult::GameOver => {
+            SceneResult::GameOver(summary) => {
+                gameover.borrow_mut().set_summary(summary);
                 scene = gameover.borrow_mut();
             }
             SceneResult::Quit => break,
@@ -56,14 +223,44 @@ fn main() {
     }
 }
 
+/// Finds the value following a command line flag, e.g. `--rotation ars`
+fn arg_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|index| args.get(index + 1))
+        .map(|value| &value[..])
+}
+
+/// Parses the value following a command line flag, exiting if it is invalid
+fn parse_arg<T>(args: &[String], flag: &str) -> Option<T>
+    where T: FromStr<Err = String>
+{
+    arg_value(args, flag).map(|value| value.parse().unwrap_or_else(|e| exit_with(e)))
+}
+
+/// Reports an invalid command line on stderr and exits
+fn exit_with<T>(message: String) -> T {
+    let _ = writeln!(io::stderr(), "{}", message);
+    std::process::exit(1);
+}
+
 fn create_main_menu(config: Config, window: &GameWindow) -> Menu {
     menu![
         (config, window.piston_window.factory.clone()),
-        "New Game" => MenuResult::NewGame,
+        "New Game" => MenuResult::ModeMenu,
         "Quit" => MenuResult::Quit
     ]
 }
 
+fn create_mode_menu(config: Config, window: &GameWindow) -> Menu {
+    let mut menu = Menu::new(config, window.piston_window.factory.clone());
+    for &mode in GameModeType::all().iter() {
+        menu.add_item(mode.create().name(), MenuResult::StartMode(mode));
+    }
+    menu.add_item("Back", MenuResult::MainMenu);
+    menu
+}
+
 fn create_pause_menu(config: Config, window: &GameWindow) -> Menu {
     menu![
         (config, window.piston_window.factory.clone()),
@@ -0,0 +1,57 @@
+use leveling::LevelingSystemType;
+use scoring::ScoringSystemType;
+
+/// A way of playing a game, with its own goal. The engine checks whether
+/// the goal has been reached after every tick and every locked piece.
+pub trait GameMode {
+    /// The name shown in the mode menu and on the game over screen
+    fn name(&self) -> &'static str;
+
+    /// Whether the goal has been reached, which ends the game
+    fn is_complete(&self, stats: &GameStats) -> bool;
+
+    /// The labelled values shown under the score while playing
+    fn hud_fields(&self, stats: &GameStats) -> Vec<(&'static str, String)>;
+
+    /// The lines shown on the game over screen
+    fn summary(&self, stats: &GameStats) -> Vec<String>;
+
+    /// Whether topping out ends the game. If it doesn't, the stack is
+    /// cleared away and play carries on.
+    fn can_top_out(&self) -> bool {
+        true
+    }
+
+    /// The scoring system the mode is played with, or `None` to use the
+    /// one in the config
+    fn scoring_system(&self) -> Option<ScoringSystemType> {
+        None
+    }
+
+    /// The leveling system the mode is played with, or `None` to use the
+    /// one in the config
+    fn leveling_system(&self) -> Option<LevelingSystemType> {
+        None
+    }
+}
+
+/// What a `GameMode` knows about the game so far
+#[derive(Copy, Clone)]
+pub struct GameStats {
+    /// How long, in seconds, the game has been played for
+    pub time: f64,
+    pub score: u32,
+    pub level: u32,
+    pub lines_cleared: u32,
+    pub pieces_locked: u32,
+}
+
+/// Formats a number of seconds as minutes, seconds and hundredths, e.g.
+/// "1:05.30"
+pub fn format_time(seconds: f64) -> String {
+    let hundredths = (seconds.max(0.0) * 100.0) as u64;
+    format!("{}:{:02}.{:02}",
+            hundredths / 6000,
+            hundredths / 100 % 60,
+            hundredths % 100)
+}
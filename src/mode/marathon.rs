@@ -0,0 +1,25 @@
+use mode::{GameMode, GameStats};
+
+/// Clear the given number of lines, speeding up as the level increases
+pub struct MarathonMode(pub u32);
+
+impl GameMode for MarathonMode {
+    fn name(&self) -> &'static str {
+        "Marathon"
+    }
+
+    fn is_complete(&self, stats: &GameStats) -> bool {
+        stats.lines_cleared >= self.0
+    }
+
+    fn hud_fields(&self, stats: &GameStats) -> Vec<(&'static str, String)> {
+        vec![("Level", format!("{}", stats.level)),
+             ("Lines", format!("{}/{}", stats.lines_cleared, self.0))]
+    }
+
+    fn summary(&self, stats: &GameStats) -> Vec<String> {
+        vec![format!("Score: {}", stats.score),
+             format!("Lines: {}", stats.lines_cleared),
+             format!("Level: {}", stats.level)]
+    }
+}
@@ -0,0 +1,102 @@
+use std::str::FromStr;
+
+mod game_mode;
+mod marathon;
+mod sprint;
+mod ultra;
+mod zen;
+
+pub use mode::game_mode::{format_time, GameMode, GameStats};
+pub use mode::marathon::MarathonMode;
+pub use mode::sprint::SprintMode;
+pub use mode::ultra::UltraMode;
+pub use mode::zen::ZenMode;
+
+/// The built in game modes that can be selected in the `Config`
+#[derive(Copy, Clone, PartialEq)]
+pub enum GameModeType {
+    /// Clear 150 lines
+    Marathon,
+    /// Clear 40 lines as fast as possible
+    Sprint,
+    /// Score as much as possible in 2 minutes
+    Ultra,
+    /// Play forever, without topping out
+    Zen,
+}
+
+impl GameModeType {
+    pub fn create(&self) -> Box<GameMode> {
+        match *self {
+            GameModeType::Marathon => Box::new(MarathonMode(150)),
+            GameModeType::Sprint => Box::new(SprintMode(40)),
+            GameModeType::Ultra => Box::new(UltraMode(120.0)),
+            GameModeType::Zen => Box::new(ZenMode),
+        }
+    }
+
+    /// Every mode, in the order they are offered in the menu
+    pub fn all() -> [GameModeType; 4] {
+        [GameModeType::Marathon, GameModeType::Sprint, GameModeType::Ultra, GameModeType::Zen]
+    }
+}
+
+impl FromStr for GameModeType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<GameModeType, String> {
+        match &s.to_lowercase()[..] {
+            "marathon" => Ok(GameModeType::Marathon),
+            "sprint" | "40l" => Ok(GameModeType::Sprint),
+            "ultra" | "blitz" => Ok(GameModeType::Ultra),
+            "zen" => Ok(GameModeType::Zen),
+            _ => Err(format!("Unknown game mode: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GameModeType, GameStats};
+
+    fn stats(time: f64, lines_cleared: u32) -> GameStats {
+        GameStats {
+            time: time,
+            score: 0,
+            level: 1,
+            lines_cleared: lines_cleared,
+            pieces_locked: 0,
+        }
+    }
+
+    #[test]
+    fn marathon_is_complete_after_150_lines() {
+        let mode = GameModeType::Marathon.create();
+        assert!(!mode.is_complete(&stats(600.0, 149)));
+        assert!(mode.is_complete(&stats(600.0, 150)));
+        assert!(mode.can_top_out());
+    }
+
+    #[test]
+    fn sprint_is_complete_after_40_lines() {
+        let mode = GameModeType::Sprint.create();
+        assert!(!mode.is_complete(&stats(60.0, 39)));
+        assert!(mode.is_complete(&stats(60.0, 40)));
+        assert!(mode.can_top_out());
+    }
+
+    #[test]
+    fn ultra_is_complete_after_two_minutes() {
+        let mode = GameModeType::Ultra.create();
+        assert!(!mode.is_complete(&stats(119.99, 200)));
+        assert!(mode.is_complete(&stats(120.0, 0)));
+        assert!(mode.can_top_out());
+    }
+
+    #[test]
+    fn zen_is_never_complete_or_topped_out() {
+        let mode = GameModeType::Zen.create();
+        assert!(!mode.is_complete(&stats(3600.0, 1000)));
+        assert!(!mode.can_top_out());
+    }
+}
@@ -0,0 +1,32 @@
+use mode::{format_time, GameMode, GameStats};
+
+/// Clear the given number of lines as fast as possible
+pub struct SprintMode(pub u32);
+
+impl GameMode for SprintMode {
+    fn name(&self) -> &'static str {
+        "Sprint"
+    }
+
+    fn is_complete(&self, stats: &GameStats) -> bool {
+        stats.lines_cleared >= self.0
+    }
+
+    fn hud_fields(&self, stats: &GameStats) -> Vec<(&'static str, String)> {
+        // Lines cleared past the goal by the last piece don't count
+        let lines = stats.lines_cleared.min(self.0);
+        vec![("Lines", format!("{}/{}", lines, self.0)), ("Time", format_time(stats.time))]
+    }
+
+    fn summary(&self, stats: &GameStats) -> Vec<String> {
+        let pieces_per_second = if stats.time > 0.0 {
+            stats.pieces_locked as f64 / stats.time
+        } else {
+            0.0
+        };
+
+        vec![format!("Time: {}", format_time(stats.time)),
+             format!("Lines: {}/{}", stats.lines_cleared.min(self.0), self.0),
+             format!("Pieces: {} ({:.2} per second)", stats.pieces_locked, pieces_per_second)]
+    }
+}
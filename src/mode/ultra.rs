@@ -0,0 +1,31 @@
+use mode::{format_time, GameMode, GameStats};
+use scoring::ScoringSystemType;
+
+/// Score as many points as possible before the time limit, in seconds,
+/// runs out
+pub struct UltraMode(pub f64);
+
+impl GameMode for UltraMode {
+    fn name(&self) -> &'static str {
+        "Ultra"
+    }
+
+    fn is_complete(&self, stats: &GameStats) -> bool {
+        stats.time >= self.0
+    }
+
+    fn hud_fields(&self, stats: &GameStats) -> Vec<(&'static str, String)> {
+        vec![("Time", format_time(self.0 - stats.time)),
+             ("Lines", format!("{}", stats.lines_cleared))]
+    }
+
+    fn summary(&self, stats: &GameStats) -> Vec<String> {
+        vec![format!("Score: {}", stats.score), format!("Lines: {}", stats.lines_cleared)]
+    }
+
+    /// A score attack needs T-spins, combos and back-to-backs to be worth
+    /// going for
+    fn scoring_system(&self) -> Option<ScoringSystemType> {
+        Some(ScoringSystemType::Guideline)
+    }
+}
@@ -0,0 +1,28 @@
+use mode::{GameMode, GameStats};
+
+/// Play for as long as you like. Topping out clears the stack instead of
+/// ending the game.
+pub struct ZenMode;
+
+impl GameMode for ZenMode {
+    fn name(&self) -> &'static str {
+        "Zen"
+    }
+
+    fn is_complete(&self, _: &GameStats) -> bool {
+        false
+    }
+
+    fn hud_fields(&self, stats: &GameStats) -> Vec<(&'static str, String)> {
+        vec![("Level", format!("{}", stats.level)),
+             ("Lines", format!("{}", stats.lines_cleared))]
+    }
+
+    fn summary(&self, stats: &GameStats) -> Vec<String> {
+        vec![format!("Score: {}", stats.score), format!("Lines: {}", stats.lines_cleared)]
+    }
+
+    fn can_top_out(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,589 @@
+use std::fs::File;
+use std::io::Read;
+use std::str::FromStr;
+
+use config::Config;
+use grid::Grid;
+use rotation::{Cells, RotationSystem};
+use tetromino::{Direction, Tetromino, TetrominoKind};
+
+static TETROMINOES: &'static str = include_str!("../assets/pieces/tetrominoes.txt");
+static PENTOMINOES: &'static str = include_str!("../assets/pieces/pentominoes.txt");
+
+/// The cells a piece occupies within its square bounding box, indexed as
+/// `cells[y][x]`
+pub type PieceCells = Vec<Vec<bool>>;
+
+/// How a piece is kicked when a rotation doesn't fit where it is
+#[derive(Clone)]
+pub enum Kicks {
+    /// Asks the configured rotation system, treating the piece as a
+    /// tetromino of the given kind
+    RotationSystem(TetrominoKind),
+    /// The offsets to try for each rotation, indexed as `table[from][to]`
+    Table(Vec<Vec<Vec<(i32, i32)>>>),
+}
+
+/// Everything needed to build one kind of piece
+#[derive(Clone)]
+pub struct PieceDefinition {
+    pub name: String,
+    /// Which tetromino this piece is, if it is one. Tetromino specific
+    /// rules such as T-spins only apply to these.
+    pub kind: Option<TetrominoKind>,
+    pub color: [f32; 3],
+    /// The cells of the piece in North, East, South, West order
+    pub states: Vec<PieceCells>,
+    pub spawn_direction: Direction,
+    /// How far the piece is moved from where it would normally spawn
+    pub spawn_offset: (i32, i32),
+    pub kicks: Kicks,
+}
+
+impl PieceDefinition {
+    /// Where the top-left corner of the bounding box is placed when the
+    /// piece spawns. Pieces are centred, with their lowest row in the
+    /// lowest hidden row, before the spawn offset is applied.
+    pub fn spawn_position(&self, config: &Config) -> (i32, i32) {
+        let cells = &self.states[self.spawn_direction as usize];
+        let size = cells.len() as i32;
+        let bottom = cells.iter().rposition(|row| row.iter().any(|&cell| cell)).unwrap_or(0);
+
+        ((config.grid_size.0 as i32 - size) / 2 + self.spawn_offset.0,
+         config.hidden_rows as i32 - 1 - bottom as i32 + self.spawn_offset.1)
+    }
+}
+
+/// The bundled piece sets that can be selected in the `Config`
+#[derive(Copy, Clone, PartialEq)]
+pub enum PieceSetType {
+    /// The seven tetrominoes as the rotation system defines them
+    Standard,
+    /// The seven tetrominoes and the SRS kick tables, from the bundled
+    /// data file
+    Tetrominoes,
+    /// The eighteen one-sided pentominoes
+    Pentominoes,
+    /// The tetrominoes and the pentominoes together
+    Mixed,
+}
+
+impl PieceSetType {
+    pub fn create(&self, rotation_system: &RotationSystem, config: &Config) -> PieceSet {
+        match *self {
+            PieceSetType::Standard => PieceSet::standard(rotation_system, config),
+            PieceSetType::Tetrominoes => PieceSet::tetrominoes(),
+            PieceSetType::Pentominoes => PieceSet::pentominoes(),
+            PieceSetType::Mixed => PieceSet::mixed(),
+        }
+    }
+}
+
+impl FromStr for PieceSetType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<PieceSetType, String> {
+        match &s.to_lowercase()[..] {
+            "standard" => Ok(PieceSetType::Standard),
+            "tetrominoes" | "tetromino" => Ok(PieceSetType::Tetrominoes),
+            "pentominoes" | "pentomino" => Ok(PieceSetType::Pentominoes),
+            "mixed" => Ok(PieceSetType::Mixed),
+            _ => Err(format!("Unknown piece set: {}", s)),
+        }
+    }
+}
+
+/// The pieces a game is played with. Pieces are referred to by their
+/// index in the set.
+#[derive(Clone)]
+pub struct PieceSet {
+    pieces: Vec<PieceDefinition>,
+}
+
+impl PieceSet {
+    /// The seven tetrominoes, shaped, placed and kicked by the rotation
+    /// system
+    pub fn standard(rotation_system: &RotationSystem, config: &Config) -> PieceSet {
+        let pieces = TetrominoKind::all()
+            .iter()
+            .map(|&kind| {
+                let mut piece = PieceDefinition {
+                    name: tetromino_name(kind).to_string(),
+                    kind: Some(kind),
+                    color: tetromino_color(kind),
+                    states: rotation_system.states(kind).iter().map(piece_cells).collect(),
+                    spawn_direction: rotation_system.spawn_direction(kind),
+                    spawn_offset: (0, 0),
+                    kicks: Kicks::RotationSystem(kind),
+                };
+
+                // Rotation systems place pieces themselves, so work out how
+                // far that is from the usual spawn position
+                let usual = piece.spawn_position(config);
+                let spawn = rotation_system.spawn_position(kind, config);
+                piece.spawn_offset = (spawn.0 - usual.0, spawn.1 - usual.1);
+                piece
+            })
+            .collect();
+
+        PieceSet { pieces: pieces }
+    }
+
+    /// The bundled tetromino set
+    pub fn tetrominoes() -> PieceSet {
+        PieceSet::parse(TETROMINOES).expect("The bundled tetrominoes are invalid")
+    }
+
+    /// The bundled pentomino set
+    pub fn pentominoes() -> PieceSet {
+        PieceSet::parse(PENTOMINOES).expect("The bundled pentominoes are invalid")
+    }
+
+    /// The bundled tetrominoes and pentominoes, in a single set
+    pub fn mixed() -> PieceSet {
+        let mut set = PieceSet::tetrominoes();
+        set.pieces.extend(PieceSet::pentominoes().pieces);
+        set
+    }
+
+    /// Reads a piece set from a file. See `assets/pieces/tetrominoes.txt`
+    /// for a description of the format.
+    pub fn load(path: &str) -> Result<PieceSet, String> {
+        let mut source = String::new();
+        File::open(path)
+            .and_then(|mut file| file.read_to_string(&mut source))
+            .map_err(|e| format!("Unable to read {}: {}", path, e))?;
+
+        PieceSet::parse(&source).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    /// Parses the contents of a piece set file
+    pub fn parse(source: &str) -> Result<PieceSet, String> {
+        let mut pieces = Vec::new();
+        let mut piece: Option<PieceBuilder> = None;
+        let mut lines = source.lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.split('#').next().unwrap().trim()))
+            .filter(|&(_, line)| !line.is_empty())
+            .peekable();
+
+        while let Some((number, line)) = lines.next() {
+            let mut words = line.split_whitespace();
+            let keyword = words.next().unwrap();
+            let args: Vec<&str> = words.collect();
+
+            if keyword == "piece" {
+                if args.len() != 1 {
+                    return Err(format!("line {}: expected a piece name", number));
+                }
+                if let Some(previous) = piece.take() {
+                    pieces.push(previous.build()?);
+                }
+                piece = Some(PieceBuilder::new(args[0]));
+                continue;
+            }
+
+            let current = match piece {
+                Some(ref mut current) => current,
+                None => return Err(format!("line {}: expected `piece` first", number)),
+            };
+
+            let result = match keyword {
+                "color" => parse_color(&args).map(|color| current.color = Some(color)),
+                "spawn" => parse_spawn(&args).map(|offset| current.spawn_offset = offset),
+                "kick" => current.add_kicks(&args),
+                "shape" => {
+                    let mut rows = Vec::new();
+                    while let Some(&(_, row)) = lines.peek() {
+                        if !row.chars().all(|c| c == 'X' || c == '.') {
+                            break;
+                        }
+                        rows.push(row.chars().map(|c| c == 'X').collect());
+                        lines.next();
+                    }
+                    current.add_shape(rows)
+                }
+                _ => Err(format!("unknown keyword `{}`", keyword)),
+            };
+
+            result.map_err(|e| format!("line {}: {}", number, e))?;
+        }
+
+        match piece {
+            Some(last) => pieces.push(last.build()?),
+            None => return Err("no pieces were defined".to_string()),
+        }
+
+        Ok(PieceSet { pieces: pieces })
+    }
+
+    pub fn len(&self) -> usize {
+        self.pieces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pieces.is_empty()
+    }
+
+    pub fn get(&self, piece: usize) -> &PieceDefinition {
+        &self.pieces[piece]
+    }
+
+    /// The index of the given tetromino, if it is in the set
+    pub fn find(&self, kind: TetrominoKind) -> Option<usize> {
+        self.pieces.iter().position(|piece| piece.kind == Some(kind))
+    }
+
+    /// The offsets to try, in order, when `tetromino` rotates to face `to`
+    pub fn kicks(&self,
+                 tetromino: &Tetromino,
+                 to: Direction,
+                 grid: &Grid,
+                 rotation_system: &RotationSystem)
+                 -> Vec<(i32, i32)> {
+        match self.pieces[tetromino.piece].kicks {
+            Kicks::RotationSystem(kind) => rotation_system.kicks(kind, tetromino, to, grid),
+            Kicks::Table(ref table) => table[tetromino.direction() as usize][to as usize].clone(),
+        }
+    }
+}
+
+/// Collects the parts of a piece as its definition is parsed
+struct PieceBuilder {
+    name: String,
+    color: Option<[f32; 3]>,
+    states: Vec<PieceCells>,
+    spawn_offset: (i32, i32),
+    default_kicks: Vec<(i32, i32)>,
+    kicks: Vec<(usize, usize, Vec<(i32, i32)>)>,
+}
+
+impl PieceBuilder {
+    fn new(name: &str) -> PieceBuilder {
+        PieceBuilder {
+            name: name.to_string(),
+            color: None,
+            states: Vec::new(),
+            spawn_offset: (0, 0),
+            default_kicks: vec![(0, 0)],
+            kicks: Vec::new(),
+        }
+    }
+
+    fn add_shape(&mut self, rows: PieceCells) -> Result<(), String> {
+        if rows.is_empty() || rows.iter().any(|row| row.len() != rows.len()) {
+            return Err("shapes must be square".to_string());
+        }
+        // A piece without blocks never lands, so it would fall forever
+        if !rows.iter().any(|row| row.iter().any(|&cell| cell)) {
+            return Err("shapes must have at least one block".to_string());
+        }
+        if let Some(first) = self.states.first() {
+            if first.len() != rows.len() {
+                return Err("every shape of a piece must be the same size".to_string());
+            }
+        }
+        if self.states.len() == 4 {
+            return Err("a piece has at most four shapes".to_string());
+        }
+
+        self.states.push(rows);
+        Ok(())
+    }
+
+    fn add_kicks(&mut self, args: &[&str]) -> Result<(), String> {
+        if args.is_empty() {
+            return Err("expected a rotation such as NE, or *".to_string());
+        }
+
+        let offsets = args[1..].iter()
+            .map(|offset| parse_offset(offset))
+            .collect::<Result<_, _>>()?;
+
+        if args[0] == "*" {
+            self.default_kicks = offsets;
+            return Ok(());
+        }
+
+        let mut directions = args[0].chars().map(direction_index);
+        match (directions.next(), directions.next(), directions.next()) {
+            (Some(Some(from)), Some(Some(to)), None) if (from + 2) % 4 == to => {
+                Err(format!("invalid rotation `{}`, 180 kicks are set in the config", args[0]))
+            }
+            (Some(Some(from)), Some(Some(to)), None) if from != to => {
+                self.kicks.push((from, to, offsets));
+                Ok(())
+            }
+            _ => Err(format!("invalid rotation `{}`", args[0])),
+        }
+    }
+
+    fn build(mut self) -> Result<PieceDefinition, String> {
+        let color = match self.color {
+            Some(color) => color,
+            None => return Err(format!("piece {} has no color", self.name)),
+        };
+        if self.states.is_empty() {
+            return Err(format!("piece {} has no shape", self.name));
+        }
+
+        // Shapes that were left out are the previous one turned clockwise
+        while self.states.len() < 4 {
+            let next = rotate_clockwise(self.states.last().unwrap());
+            self.states.push(next);
+        }
+
+        let mut table = vec![vec![self.default_kicks.clone(); 4]; 4];
+        for (from, to, offsets) in self.kicks {
+            table[from][to] = offsets;
+        }
+
+        let cells = self.states[0].iter().flat_map(|row| row.iter()).filter(|&&cell| cell).count();
+
+        Ok(PieceDefinition {
+            kind: if cells == 4 { tetromino_kind(&self.name) } else { None },
+            name: self.name,
+            color: color,
+            states: self.states,
+            spawn_direction: Direction::North,
+            spawn_offset: self.spawn_offset,
+            kicks: Kicks::Table(table),
+        })
+    }
+}
+
+fn parse_color(args: &[&str]) -> Result<[f32; 3], String> {
+    if args.len() != 3 {
+        return Err("expected a color as three numbers".to_string());
+    }
+
+    let mut color = [0.0; 3];
+    for (channel, value) in color.iter_mut().zip(args) {
+        *channel = value.parse().map_err(|_| format!("invalid color value `{}`", value))?;
+    }
+
+    Ok(color)
+}
+
+fn parse_spawn(args: &[&str]) -> Result<(i32, i32), String> {
+    if args.len() != 2 {
+        return Err("expected a spawn offset as two numbers".to_string());
+    }
+
+    parse_pair(args[0], args[1]).ok_or_else(|| "invalid spawn offset".to_string())
+}
+
+fn parse_offset(offset: &str) -> Result<(i32, i32), String> {
+    let parts: Vec<&str> = offset.split(',').collect();
+    if parts.len() != 2 {
+        return Err(format!("invalid kick offset `{}`", offset));
+    }
+
+    parse_pair(parts[0], parts[1]).ok_or_else(|| format!("invalid kick offset `{}`", offset))
+}
+
+fn parse_pair(x: &str, y: &str) -> Option<(i32, i32)> {
+    match (x.parse(), y.parse()) {
+        (Ok(x), Ok(y)) => Some((x, y)),
+        _ => None,
+    }
+}
+
+fn direction_index(c: char) -> Option<usize> {
+    match c {
+        'N' => Some(Direction::North as usize),
+        'E' => Some(Direction::East as usize),
+        'S' => Some(Direction::South as usize),
+        'W' => Some(Direction::West as usize),
+        _ => None,
+    }
+}
+
+fn piece_cells(cells: &Cells) -> PieceCells {
+    cells.iter().map(|row| row.iter().map(|&cell| cell != 0).collect()).collect()
+}
+
+fn rotate_clockwise(cells: &PieceCells) -> PieceCells {
+    let size = cells.len();
+    (0..size).map(|y| (0..size).map(|x| cells[size - 1 - x][y]).collect()).collect()
+}
+
+fn tetromino_kind(name: &str) -> Option<TetrominoKind> {
+    TetrominoKind::all().iter().cloned().find(|&kind| tetromino_name(kind) == name)
+}
+
+fn tetromino_name(kind: TetrominoKind) -> &'static str {
+    match kind {
+        TetrominoKind::I => "I",
+        TetrominoKind::J => "J",
+        TetrominoKind::L => "L",
+        TetrominoKind::O => "O",
+        TetrominoKind::S => "S",
+        TetrominoKind::T => "T",
+        TetrominoKind::Z => "Z",
+    }
+}
+
+fn tetromino_color(kind: TetrominoKind) -> [f32; 3] {
+    match kind {
+        TetrominoKind::I => [0.0, 1.0, 1.0],
+        TetrominoKind::J => [0.0, 0.0, 1.0],
+        TetrominoKind::L => [0.8, 0.5, 0.0],
+        TetrominoKind::O => [1.0, 1.0, 0.0],
+        TetrominoKind::S => [0.0, 0.75, 0.0],
+        TetrominoKind::T => [1.0, 0.4, 0.7],
+        TetrominoKind::Z => [1.0, 0.0, 0.0],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Kicks, PieceCells, PieceSet};
+    use tetromino::TetrominoKind;
+
+    fn cells(rows: &[&str]) -> PieceCells {
+        rows.iter().map(|row| row.chars().map(|c| c == 'X').collect()).collect()
+    }
+
+    fn parse_error(source: &str) -> String {
+        match PieceSet::parse(source) {
+            Ok(_) => panic!("parsed an invalid piece set"),
+            Err(e) => e,
+        }
+    }
+
+    #[test]
+    fn parses_a_piece() {
+        let piece_set = PieceSet::parse("# A comment\n\
+                                         piece P   # and another\n\
+                                         color 0.5 1 0\n\
+                                         spawn -1 2\n\
+                                         shape\n\
+                                         XX.\n\
+                                         XX.\n\
+                                         X..\n")
+            .unwrap();
+
+        assert_eq!(piece_set.len(), 1);
+        let piece = piece_set.get(0);
+        assert_eq!(piece.name, "P");
+        assert_eq!(piece.color, [0.5, 1.0, 0.0]);
+        assert_eq!(piece.spawn_offset, (-1, 2));
+        assert_eq!(piece.states[0], cells(&["XX.", "XX.", "X.."]));
+    }
+
+    #[test]
+    fn missing_shapes_are_the_previous_one_turned_clockwise() {
+        let piece_set = PieceSet::parse("piece L\ncolor 1 1 1\nshape\n..X\nXXX\n...\n").unwrap();
+        let states = &piece_set.get(0).states;
+
+        assert_eq!(states.len(), 4);
+        assert_eq!(states[1], cells(&[".X.", ".X.", ".XX"]));
+        assert_eq!(states[2], cells(&["...", "XXX", "X.."]));
+        assert_eq!(states[3], cells(&["XX.", ".X.", ".X."]));
+    }
+
+    #[test]
+    fn given_shapes_are_kept() {
+        let piece_set = PieceSet::parse("piece S\ncolor 1 1 1\n\
+                                         shape\n.XX\nXX.\n...\n\
+                                         shape\nX..\nXX.\n.X.\n")
+            .unwrap();
+        let states = &piece_set.get(0).states;
+
+        assert_eq!(states[1], cells(&["X..", "XX.", ".X."]));
+        assert_eq!(states[2], cells(&[".XX", "XX.", "..."]));
+    }
+
+    #[test]
+    fn parses_kick_tables() {
+        let piece_set = PieceSet::parse("piece T\ncolor 1 1 1\nshape\n.X.\nXXX\n...\n\
+                                         kick * 0,0 0,-1\n\
+                                         kick NE 0,0 -1,0 -1,-1\n")
+            .unwrap();
+
+        match piece_set.get(0).kicks {
+            Kicks::Table(ref table) => {
+                assert_eq!(table[0][1], vec![(0, 0), (-1, 0), (-1, -1)]);
+                assert_eq!(table[1][0], vec![(0, 0), (0, -1)]);
+                assert_eq!(table[3][0], vec![(0, 0), (0, -1)]);
+            }
+            Kicks::RotationSystem(_) => panic!("expected a kick table"),
+        }
+    }
+
+    #[test]
+    fn pieces_without_kicks_only_rotate_in_place() {
+        let piece_set = PieceSet::parse("piece T\ncolor 1 1 1\nshape\n.X.\nXXX\n...\n").unwrap();
+
+        match piece_set.get(0).kicks {
+            Kicks::Table(ref table) => assert_eq!(table[2][1], vec![(0, 0)]),
+            Kicks::RotationSystem(_) => panic!("expected a kick table"),
+        }
+    }
+
+    #[test]
+    fn only_four_block_pieces_named_after_a_tetromino_are_tetrominoes() {
+        let piece_set = PieceSet::parse("piece T\ncolor 1 1 1\nshape\n.X.\nXXX\n...\n\
+                                         piece Q\ncolor 1 1 1\nshape\nXX\nXX\n\
+                                         piece I\ncolor 1 1 1\nshape\n.....\nXXXXX\n.....\n\
+                                         .....\n.....\n")
+            .unwrap();
+
+        assert!(piece_set.get(0).kind == Some(TetrominoKind::T));
+        assert!(piece_set.get(1).kind.is_none());
+        assert!(piece_set.get(2).kind.is_none());
+        assert_eq!(piece_set.find(TetrominoKind::T), Some(0));
+        assert_eq!(piece_set.find(TetrominoKind::I), None);
+    }
+
+    #[test]
+    fn bundled_sets_parse() {
+        assert_eq!(PieceSet::tetrominoes().len(), 7);
+        assert_eq!(PieceSet::pentominoes().len(), 18);
+        assert_eq!(PieceSet::mixed().len(), 25);
+    }
+
+    #[test]
+    fn reports_where_a_file_is_malformed() {
+        assert_eq!(parse_error(""), "no pieces were defined");
+        assert_eq!(parse_error("color 1 1 1\n"), "line 1: expected `piece` first");
+        assert_eq!(parse_error("piece\n"), "line 1: expected a piece name");
+        assert_eq!(parse_error("piece P\ncolour 1 1 1\n"),
+                   "line 2: unknown keyword `colour`");
+        assert_eq!(parse_error("piece P\ncolor 1 1\n"),
+                   "line 2: expected a color as three numbers");
+        assert_eq!(parse_error("piece P\ncolor 1 red 1\n"),
+                   "line 2: invalid color value `red`");
+        assert_eq!(parse_error("piece P\n\ncolor 1 1 1\nspawn 1\n"),
+                   "line 4: expected a spawn offset as two numbers");
+        assert_eq!(parse_error("piece P\ncolor 1 1 1\n"), "piece P has no shape");
+        assert_eq!(parse_error("piece P\nshape\nX\n"), "piece P has no color");
+    }
+
+    #[test]
+    fn rejects_bad_shapes() {
+        assert_eq!(parse_error("piece P\ncolor 1 1 1\nshape\nXX\nX.\nX.\n"),
+                   "line 3: shapes must be square");
+        assert_eq!(parse_error("piece P\ncolor 1 1 1\nshape\n..\n..\n"),
+                   "line 3: shapes must have at least one block");
+        assert_eq!(parse_error("piece P\ncolor 1 1 1\nshape\nXX\nXX\nshape\nX..\nX..\nX..\n"),
+                   "line 6: every shape of a piece must be the same size");
+        assert_eq!(parse_error("piece P\ncolor 1 1 1\nshape\nX\nshape\nX\nshape\nX\nshape\nX\n\
+                                shape\nX\n"),
+                   "line 11: a piece has at most four shapes");
+    }
+
+    #[test]
+    fn rejects_bad_kicks() {
+        let piece = "piece P\ncolor 1 1 1\nshape\nXX\nX.\n";
+        assert_eq!(parse_error(&format!("{}kick\n", piece)),
+                   "line 6: expected a rotation such as NE, or *");
+        assert_eq!(parse_error(&format!("{}kick NE 0,0 1\n", piece)),
+                   "line 6: invalid kick offset `1`");
+        assert_eq!(parse_error(&format!("{}kick NX 0,0\n", piece)),
+                   "line 6: invalid rotation `NX`");
+        assert_eq!(parse_error(&format!("{}kick NS 0,0\n", piece)),
+                   "line 6: invalid rotation `NS`, 180 kicks are set in the config");
+    }
+}
@@ -0,0 +1,43 @@
+use rand::Rng;
+
+use randomizer::{PieceRng, Randomizer};
+
+/// Deals pieces from a shuffled bag holding `copies` of each piece,
+/// refilling it once it is empty. With one copy of the seven tetrominoes
+/// this is the guideline "7-bag", which never goes more than 12 pieces
+/// without any given piece.
+pub struct BagRandomizer {
+    copies: usize,
+    pieces: usize,
+    bag: Vec<usize>,
+    rng: PieceRng,
+}
+
+impl BagRandomizer {
+    pub fn new(copies: usize, pieces: usize, rng: PieceRng) -> BagRandomizer {
+        BagRandomizer {
+            copies: copies,
+            pieces: pieces,
+            bag: Vec::new(),
+            rng: rng,
+        }
+    }
+
+    fn refill(&mut self) {
+        for _ in 0..self.copies {
+            self.bag.extend(0..self.pieces);
+        }
+
+        self.rng.shuffle(&mut self.bag);
+    }
+}
+
+impl Randomizer for BagRandomizer {
+    fn next(&mut self) -> usize {
+        if self.bag.is_empty() {
+            self.refill();
+        }
+
+        self.bag.pop().unwrap()
+    }
+}
@@ -0,0 +1,74 @@
+use rand::Rng;
+
+use piece_set::PieceSet;
+use randomizer::{PieceRng, Randomizer};
+use tetromino::TetrominoKind;
+
+/// The history randomizer from Tetris: The Grand Master. It remembers the
+/// last four pieces and rolls up to `rolls` times in total (so rerolls up
+/// to `rolls - 1` times) to avoid handing out one of them again. The
+/// history starts out full of Z pieces and the first piece is never an S,
+/// Z or O, so a game never opens with an unavoidable overhang. Sets
+/// without those tetrominoes start with an empty history and may open
+/// with any piece.
+pub struct HistoryRandomizer {
+    rolls: u32,
+    pieces: usize,
+    openers: Vec<usize>,
+    history: [Option<usize>; 4],
+    first: bool,
+    rng: PieceRng,
+}
+
+impl HistoryRandomizer {
+    pub fn new(rolls: u32, piece_set: &PieceSet, rng: PieceRng) -> HistoryRandomizer {
+        let mut openers: Vec<usize> =
+            [TetrominoKind::I, TetrominoKind::J, TetrominoKind::L, TetrominoKind::T]
+                .iter()
+                .filter_map(|&kind| piece_set.find(kind))
+                .collect();
+        if openers.is_empty() {
+            openers = (0..piece_set.len()).collect();
+        }
+
+        HistoryRandomizer {
+            rolls: rolls,
+            pieces: piece_set.len(),
+            openers: openers,
+            history: [piece_set.find(TetrominoKind::Z); 4],
+            first: true,
+            rng: rng,
+        }
+    }
+
+    fn roll(&mut self) -> usize {
+        if self.first {
+            return self.openers[self.rng.gen_range(0, self.openers.len())];
+        }
+
+        let mut piece = self.rng.gen_range(0, self.pieces);
+        for _ in 1..self.rolls {
+            if !self.history.contains(&Some(piece)) {
+                break;
+            }
+            piece = self.rng.gen_range(0, self.pieces);
+        }
+
+        piece
+    }
+}
+
+impl Randomizer for HistoryRandomizer {
+    fn next(&mut self) -> usize {
+        let piece = self.roll();
+        self.first = false;
+
+        // Push the new piece on to the front of the history
+        for i in (1..self.history.len()).rev() {
+            self.history[i] = self.history[i - 1];
+        }
+        self.history[0] = Some(piece);
+
+        piece
+    }
+}
@@ -0,0 +1,106 @@
+use std::str::FromStr;
+
+use rand::{SeedableRng, XorShiftRng};
+
+use piece_set::PieceSet;
+
+mod bag;
+mod history;
+mod nes;
+mod piece_randomizer;
+mod random;
+
+pub use randomizer::bag::BagRandomizer;
+pub use randomizer::history::HistoryRandomizer;
+pub use randomizer::nes::NesRandomizer;
+pub use randomizer::random::MemorylessRandomizer;
+pub use randomizer::piece_randomizer::Randomizer;
+
+/// The random number generator that drives every randomizer. XorShift
+/// produces the same sequence for a given seed on every platform, so a
+/// seed is all that is needed to reproduce a game's pieces.
+pub type PieceRng = XorShiftRng;
+
+/// Creates a generator from a 64 bit seed
+pub fn seeded_rng(seed: u64) -> PieceRng {
+    let low = seed as u32;
+    let high = (seed >> 32) as u32;
+
+    // XorShift can't be seeded with all zeroes, so mix in some constants
+    PieceRng::from_seed([low, high, low ^ 0x9e37_79b9, high ^ 0x7f4a_7c15])
+}
+
+/// The built in randomizers that can be selected in the `Config`
+#[derive(Copy, Clone, PartialEq)]
+pub enum RandomizerType {
+    SevenBag,
+    FourteenBag,
+    Tgm,
+    Nes,
+    Memoryless,
+}
+
+impl RandomizerType {
+    pub fn create(&self, seed: u64, piece_set: &PieceSet) -> Box<Randomizer> {
+        let rng = seeded_rng(seed);
+        let pieces = piece_set.len();
+
+        match *self {
+            RandomizerType::SevenBag => Box::new(BagRandomizer::new(1, pieces, rng)),
+            RandomizerType::FourteenBag => Box::new(BagRandomizer::new(2, pieces, rng)),
+            RandomizerType::Tgm => Box::new(HistoryRandomizer::new(4, piece_set, rng)),
+            RandomizerType::Nes => Box::new(NesRandomizer::new(pieces, rng)),
+            RandomizerType::Memoryless => Box::new(MemorylessRandomizer::new(pieces, rng)),
+        }
+    }
+}
+
+impl FromStr for RandomizerType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<RandomizerType, String> {
+        match &s.to_lowercase()[..] {
+            "7bag" | "bag" => Ok(RandomizerType::SevenBag),
+            "14bag" => Ok(RandomizerType::FourteenBag),
+            "tgm" => Ok(RandomizerType::Tgm),
+            "nes" => Ok(RandomizerType::Nes),
+            "random" | "memoryless" => Ok(RandomizerType::Memoryless),
+            _ => Err(format!("Unknown randomizer: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RandomizerType;
+    use piece_set::PieceSet;
+
+    fn sequence(randomizer: RandomizerType, seed: u64) -> Vec<usize> {
+        let mut randomizer = randomizer.create(seed, &PieceSet::tetrominoes());
+        (0..70).map(|_| randomizer.next()).collect()
+    }
+
+    #[test]
+    fn the_same_seed_deals_the_same_pieces() {
+        let randomizers = [RandomizerType::SevenBag,
+                           RandomizerType::FourteenBag,
+                           RandomizerType::Tgm,
+                           RandomizerType::Nes,
+                           RandomizerType::Memoryless];
+
+        for &randomizer in randomizers.iter() {
+            assert_eq!(sequence(randomizer, 1234), sequence(randomizer, 1234));
+            assert!(sequence(randomizer, 1234) != sequence(randomizer, 4321));
+        }
+    }
+
+    #[test]
+    fn seven_bag_deals_every_piece_once_per_bag() {
+        let pieces = sequence(RandomizerType::SevenBag, 0);
+        for bag in pieces.chunks(7) {
+            let mut bag = bag.to_vec();
+            bag.sort();
+            assert_eq!(bag, vec![0, 1, 2, 3, 4, 5, 6]);
+        }
+    }
+}
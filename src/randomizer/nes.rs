@@ -0,0 +1,37 @@
+use rand::Rng;
+
+use randomizer::{PieceRng, Randomizer};
+
+/// The randomizer from NES Tetris. It rolls a die with one more side than
+/// there are pieces, where the extra side means "reroll", and rerolls
+/// once (without the extra side) if it lands on the previous piece. The
+/// reroll is kept even if it repeats the previous piece.
+pub struct NesRandomizer {
+    pieces: usize,
+    previous: Option<usize>,
+    rng: PieceRng,
+}
+
+impl NesRandomizer {
+    pub fn new(pieces: usize, rng: PieceRng) -> NesRandomizer {
+        NesRandomizer {
+            pieces: pieces,
+            previous: None,
+            rng: rng,
+        }
+    }
+}
+
+impl Randomizer for NesRandomizer {
+    fn next(&mut self) -> usize {
+        let roll = self.rng.gen_range(0, self.pieces + 1);
+        let piece = if roll == self.pieces || Some(roll) == self.previous {
+            self.rng.gen_range(0, self.pieces)
+        } else {
+            roll
+        };
+
+        self.previous = Some(piece);
+        piece
+    }
+}
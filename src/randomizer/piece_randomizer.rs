@@ -0,0 +1,5 @@
+/// A Randomizer decides which piece the `TetrominoFactory` creates next,
+/// as an index in the `PieceSet`
+pub trait Randomizer {
+    fn next(&mut self) -> usize;
+}
@@ -0,0 +1,25 @@
+use rand::Rng;
+
+use randomizer::{PieceRng, Randomizer};
+
+/// Picks every piece uniformly at random with no memory of what came
+/// before, so droughts and floods of any piece are possible
+pub struct MemorylessRandomizer {
+    pieces: usize,
+    rng: PieceRng,
+}
+
+impl MemorylessRandomizer {
+    pub fn new(pieces: usize, rng: PieceRng) -> MemorylessRandomizer {
+        MemorylessRandomizer {
+            pieces: pieces,
+            rng: rng,
+        }
+    }
+}
+
+impl Randomizer for MemorylessRandomizer {
+    fn next(&mut self) -> usize {
+        self.rng.gen_range(0, self.pieces)
+    }
+}
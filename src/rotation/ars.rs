@@ -0,0 +1,249 @@
+use config::Config;
+use grid::Grid;
+use rotation::{Cells, RotationSystem};
+use tetromino::{Direction, Tetromino, TetrominoKind};
+
+/// The Arika Rotation System from the Tetris: The Grand Master series.
+/// Pieces spawn flat side up and rest on the bottom of their bounding
+/// box. A blocked rotation is retried one cell to the right and then one
+/// cell to the left, except for the I piece which never kicks and for J,
+/// L and T pieces blocked by the "center column rule".
+pub struct ArikaRotationSystem;
+
+impl RotationSystem for ArikaRotationSystem {
+    fn states(&self, kind: TetrominoKind) -> [Cells; 4] {
+        match kind {
+            TetrominoKind::I => I_STATES,
+            TetrominoKind::J => J_STATES,
+            TetrominoKind::L => L_STATES,
+            TetrominoKind::O => O_STATES,
+            TetrominoKind::S => S_STATES,
+            TetrominoKind::T => T_STATES,
+            TetrominoKind::Z => Z_STATES,
+        }
+    }
+
+    fn spawn_position(&self, _kind: TetrominoKind, config: &Config) -> (i32, i32) {
+        // Every piece sits one row down in its box, so spawn one row
+        // higher to keep it inside the hidden rows
+        ((config.grid_size.0 as i32 / 2) - 2, config.hidden_rows as i32 - 3)
+    }
+
+    fn kicks(&self,
+             kind: TetrominoKind,
+             tetromino: &Tetromino,
+             to: Direction,
+             grid: &Grid)
+             -> Vec<(i32, i32)> {
+        match kind {
+            TetrominoKind::I | TetrominoKind::O => vec![(0, 0)],
+            TetrominoKind::J | TetrominoKind::L | TetrominoKind::T => {
+                if self.center_column_blocked(kind, tetromino, to, grid) {
+                    vec![(0, 0)]
+                } else {
+                    vec![(0, 0), (1, 0), (-1, 0)]
+                }
+            }
+            TetrominoKind::S | TetrominoKind::Z => vec![(0, 0), (1, 0), (-1, 0)],
+        }
+    }
+}
+
+impl ArikaRotationSystem {
+    /// Scans the 3x3 area the rotated piece would cover in reading order.
+    /// If the first occupied cell it overlaps is in the center column the
+    /// piece may not kick.
+    fn center_column_blocked(&self,
+                             kind: TetrominoKind,
+                             tetromino: &Tetromino,
+                             to: Direction,
+                             grid: &Grid)
+                             -> bool {
+        let cells = self.states(kind)[to as usize];
+
+        for (y, row) in cells.iter().enumerate().take(3) {
+            for (x, cell) in row.iter().enumerate().take(3) {
+                if *cell == 0 {
+                    continue;
+                }
+
+                let grid_x = tetromino.x + x as i32;
+                let grid_y = tetromino.y + y as i32;
+
+                if grid.is_occupied(grid_x, grid_y) {
+                    return x == 1;
+                }
+            }
+        }
+
+        false
+    }
+}
+
+static I_STATES: [Cells; 4] = [// North
+                               [[0, 0, 0, 0],
+                                [1, 1, 1, 1],
+                                [0, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[0, 0, 1, 0],
+                                [0, 0, 1, 0],
+                                [0, 0, 1, 0],
+                                [0, 0, 1, 0]],
+
+                               // South
+                               [[0, 0, 0, 0],
+                                [1, 1, 1, 1],
+                                [0, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[0, 0, 1, 0],
+                                [0, 0, 1, 0],
+                                [0, 0, 1, 0],
+                                [0, 0, 1, 0]]];
+
+static J_STATES: [Cells; 4] = [// North
+                               [[0, 0, 0, 0],
+                                [1, 1, 1, 0],
+                                [0, 0, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[0, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [1, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // South
+                               [[0, 0, 0, 0],
+                                [1, 0, 0, 0],
+                                [1, 1, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[0, 1, 1, 0],
+                                [0, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]]];
+
+static L_STATES: [Cells; 4] = [// North
+                               [[0, 0, 0, 0],
+                                [1, 1, 1, 0],
+                                [1, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[1, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // South
+                               [[0, 0, 0, 0],
+                                [0, 0, 1, 0],
+                                [1, 1, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[0, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0]]];
+
+static O_STATES: [Cells; 4] = [// North
+                               [[0, 0, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[0, 0, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // South
+                               [[0, 0, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[0, 0, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0]]];
+
+static S_STATES: [Cells; 4] = [// North
+                               [[0, 0, 0, 0],
+                                [0, 1, 1, 0],
+                                [1, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[1, 0, 0, 0],
+                                [1, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // South
+                               [[0, 0, 0, 0],
+                                [0, 1, 1, 0],
+                                [1, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[1, 0, 0, 0],
+                                [1, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]]];
+
+static T_STATES: [Cells; 4] = [// North
+                               [[0, 0, 0, 0],
+                                [1, 1, 1, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[0, 1, 0, 0],
+                                [1, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // South
+                               [[0, 0, 0, 0],
+                                [0, 1, 0, 0],
+                                [1, 1, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[0, 1, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]]];
+
+static Z_STATES: [Cells; 4] = [// North
+                               [[0, 0, 0, 0],
+                                [1, 1, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[0, 0, 1, 0],
+                                [0, 1, 1, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // South
+                               [[0, 0, 0, 0],
+                                [1, 1, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[0, 0, 1, 0],
+                                [0, 1, 1, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]]];
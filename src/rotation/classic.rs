@@ -0,0 +1,27 @@
+use config::Config;
+use grid::Grid;
+use rotation::{Cells, RotationSystem, SuperRotationSystem};
+use tetromino::{Direction, Tetromino, TetrominoKind};
+
+/// The original TetRS rotation: Super Rotation System pieces that are
+/// never kicked, so any rotation that overlaps a wall or block is denied.
+pub struct ClassicRotationSystem;
+
+impl RotationSystem for ClassicRotationSystem {
+    fn states(&self, kind: TetrominoKind) -> [Cells; 4] {
+        SuperRotationSystem.states(kind)
+    }
+
+    fn spawn_position(&self, kind: TetrominoKind, config: &Config) -> (i32, i32) {
+        SuperRotationSystem.spawn_position(kind, config)
+    }
+
+    fn kicks(&self,
+             _kind: TetrominoKind,
+             _tetromino: &Tetromino,
+             _to: Direction,
+             _grid: &Grid)
+             -> Vec<(i32, i32)> {
+        vec![(0, 0)]
+    }
+}
@@ -0,0 +1,68 @@
+use std::str::FromStr;
+
+use tetromino::Direction;
+
+/// The kicks tried when a piece turns 180 degrees. These are used by every
+/// piece, whatever the rotation system.
+#[derive(Copy, Clone, PartialEq)]
+pub enum HalfTurnKicks {
+    /// The SRS+ table used by TETR.IO
+    SrsPlus,
+    /// The piece turns where it is or not at all
+    None,
+}
+
+impl HalfTurnKicks {
+    /// The offsets to try, in order, when a piece facing `from` turns
+    /// around
+    pub fn kicks(&self, from: Direction) -> Vec<(i32, i32)> {
+        match *self {
+            HalfTurnKicks::SrsPlus => SRS_PLUS_KICKS[from as usize].to_vec(),
+            HalfTurnKicks::None => vec![(0, 0)],
+        }
+    }
+}
+
+impl FromStr for HalfTurnKicks {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<HalfTurnKicks, String> {
+        match &s.to_lowercase()[..] {
+            "srs+" | "srsplus" | "tetrio" => Ok(HalfTurnKicks::SrsPlus),
+            "none" => Ok(HalfTurnKicks::None),
+            _ => Err(format!("Unknown 180 kicks: {}", s)),
+        }
+    }
+}
+
+// Offsets are (x, y) with y growing downwards, indexed by the direction
+// the piece faces before it turns
+static SRS_PLUS_KICKS: [[(i32, i32); 6]; 4] = [
+    // North -> South
+    [(0, 0), (0, -1), (1, -1), (-1, -1), (1, 0), (-1, 0)],
+    // East -> West
+    [(0, 0), (1, 0), (1, -2), (1, -1), (0, -2), (0, -1)],
+    // South -> North
+    [(0, 0), (0, 1), (-1, 1), (1, 1), (-1, 0), (1, 0)],
+    // West -> East
+    [(0, 0), (-1, 0), (-1, -2), (-1, -1), (0, -2), (0, -1)],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::HalfTurnKicks;
+    use tetromino::Direction;
+
+    #[test]
+    fn srs_plus_kicks_are_tried_in_order() {
+        assert_eq!(HalfTurnKicks::SrsPlus.kicks(Direction::North),
+                   vec![(0, 0), (0, -1), (1, -1), (-1, -1), (1, 0), (-1, 0)]);
+        assert_eq!(HalfTurnKicks::SrsPlus.kicks(Direction::East),
+                   vec![(0, 0), (1, 0), (1, -2), (1, -1), (0, -2), (0, -1)]);
+    }
+
+    #[test]
+    fn none_only_turns_in_place() {
+        assert_eq!(HalfTurnKicks::None.kicks(Direction::West), vec![(0, 0)]);
+    }
+}
@@ -0,0 +1,52 @@
+use std::str::FromStr;
+
+mod ars;
+mod classic;
+mod half_turn;
+mod nrs;
+mod rotation_system;
+mod srs;
+
+pub use rotation::ars::ArikaRotationSystem;
+pub use rotation::classic::ClassicRotationSystem;
+pub use rotation::half_turn::HalfTurnKicks;
+pub use rotation::nrs::NintendoRotationSystem;
+pub use rotation::rotation_system::{Cells, RotationSystem};
+pub use rotation::srs::SuperRotationSystem;
+
+/// The built in rotation systems that can be selected in the `Config`
+#[derive(Copy, Clone, PartialEq)]
+pub enum RotationSystemType {
+    Srs,
+    Ars,
+    Nes,
+    GameBoy,
+    Classic,
+}
+
+impl RotationSystemType {
+    pub fn create(&self) -> Box<RotationSystem> {
+        match *self {
+            RotationSystemType::Srs => Box::new(SuperRotationSystem),
+            RotationSystemType::Ars => Box::new(ArikaRotationSystem),
+            RotationSystemType::Nes => Box::new(NintendoRotationSystem::nes()),
+            RotationSystemType::GameBoy => Box::new(NintendoRotationSystem::game_boy()),
+            RotationSystemType::Classic => Box::new(ClassicRotationSystem),
+        }
+    }
+}
+
+impl FromStr for RotationSystemType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<RotationSystemType, String> {
+        match &s.to_lowercase()[..] {
+            "srs" => Ok(RotationSystemType::Srs),
+            "ars" => Ok(RotationSystemType::Ars),
+            "nes" => Ok(RotationSystemType::Nes),
+            "gameboy" | "gb" => Ok(RotationSystemType::GameBoy),
+            "classic" => Ok(RotationSystemType::Classic),
+            _ => Err(format!("Unknown rotation system: {}", s)),
+        }
+    }
+}
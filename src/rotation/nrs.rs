@@ -0,0 +1,295 @@
+use config::Config;
+use grid::Grid;
+use rotation::{Cells, RotationSystem};
+use tetromino::{Direction, Tetromino, TetrominoKind};
+
+/// The Nintendo Rotation System from the NES and Game Boy games. Pieces
+/// rotate about a fixed center cell and are never kicked. The I, S and Z
+/// pieces only have two orientations, which sit to the right of center
+/// on the NES and to the left of it on the Game Boy.
+pub struct NintendoRotationSystem {
+    left_handed: bool,
+}
+
+impl NintendoRotationSystem {
+    /// The right-handed rotation used by NES Tetris
+    pub fn nes() -> NintendoRotationSystem {
+        NintendoRotationSystem { left_handed: false }
+    }
+
+    /// The left-handed rotation used by Game Boy Tetris
+    pub fn game_boy() -> NintendoRotationSystem {
+        NintendoRotationSystem { left_handed: true }
+    }
+}
+
+impl RotationSystem for NintendoRotationSystem {
+    fn states(&self, kind: TetrominoKind) -> [Cells; 4] {
+        match (kind, self.left_handed) {
+            (TetrominoKind::I, false) => RIGHT_I_STATES,
+            (TetrominoKind::I, true) => LEFT_I_STATES,
+            (TetrominoKind::S, false) => RIGHT_S_STATES,
+            (TetrominoKind::S, true) => LEFT_S_STATES,
+            (TetrominoKind::Z, false) => RIGHT_Z_STATES,
+            (TetrominoKind::Z, true) => LEFT_Z_STATES,
+            (TetrominoKind::J, _) => J_STATES,
+            (TetrominoKind::L, _) => L_STATES,
+            (TetrominoKind::O, _) => O_STATES,
+            (TetrominoKind::T, _) => T_STATES,
+        }
+    }
+
+    fn spawn_position(&self, _kind: TetrominoKind, config: &Config) -> (i32, i32) {
+        // Pieces spawn pointing down, one row into their box
+        ((config.grid_size.0 as i32 / 2) - 2, config.hidden_rows as i32 - 3)
+    }
+
+    fn kicks(&self,
+             _kind: TetrominoKind,
+             _tetromino: &Tetromino,
+             _to: Direction,
+             _grid: &Grid)
+             -> Vec<(i32, i32)> {
+        vec![(0, 0)]
+    }
+}
+
+static J_STATES: [Cells; 4] = [// North
+                               [[0, 0, 0, 0],
+                                [1, 1, 1, 0],
+                                [0, 0, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[0, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [1, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // South
+                               [[1, 0, 0, 0],
+                                [1, 1, 1, 0],
+                                [0, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[0, 1, 1, 0],
+                                [0, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]]];
+
+static L_STATES: [Cells; 4] = [// North
+                               [[0, 0, 0, 0],
+                                [1, 1, 1, 0],
+                                [1, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[1, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // South
+                               [[0, 0, 1, 0],
+                                [1, 1, 1, 0],
+                                [0, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[0, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0]]];
+
+static O_STATES: [Cells; 4] = [// North
+                               [[0, 0, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[0, 0, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // South
+                               [[0, 0, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[0, 0, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0]]];
+
+static T_STATES: [Cells; 4] = [// North
+                               [[0, 0, 0, 0],
+                                [1, 1, 1, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[0, 1, 0, 0],
+                                [1, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // South
+                               [[0, 1, 0, 0],
+                                [1, 1, 1, 0],
+                                [0, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[0, 1, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]]];
+
+static RIGHT_I_STATES: [Cells; 4] = [// North
+                                     [[0, 0, 0, 0],
+                                      [0, 0, 0, 0],
+                                      [1, 1, 1, 1],
+                                      [0, 0, 0, 0]],
+
+                                     // East
+                                     [[0, 0, 1, 0],
+                                      [0, 0, 1, 0],
+                                      [0, 0, 1, 0],
+                                      [0, 0, 1, 0]],
+
+                                     // South
+                                     [[0, 0, 0, 0],
+                                      [0, 0, 0, 0],
+                                      [1, 1, 1, 1],
+                                      [0, 0, 0, 0]],
+
+                                     // West
+                                     [[0, 0, 1, 0],
+                                      [0, 0, 1, 0],
+                                      [0, 0, 1, 0],
+                                      [0, 0, 1, 0]]];
+
+static RIGHT_S_STATES: [Cells; 4] = [// North
+                                     [[0, 0, 0, 0],
+                                      [0, 1, 1, 0],
+                                      [1, 1, 0, 0],
+                                      [0, 0, 0, 0]],
+
+                                     // East
+                                     [[0, 1, 0, 0],
+                                      [0, 1, 1, 0],
+                                      [0, 0, 1, 0],
+                                      [0, 0, 0, 0]],
+
+                                     // South
+                                     [[0, 0, 0, 0],
+                                      [0, 1, 1, 0],
+                                      [1, 1, 0, 0],
+                                      [0, 0, 0, 0]],
+
+                                     // West
+                                     [[0, 1, 0, 0],
+                                      [0, 1, 1, 0],
+                                      [0, 0, 1, 0],
+                                      [0, 0, 0, 0]]];
+
+static RIGHT_Z_STATES: [Cells; 4] = [// North
+                                     [[0, 0, 0, 0],
+                                      [1, 1, 0, 0],
+                                      [0, 1, 1, 0],
+                                      [0, 0, 0, 0]],
+
+                                     // East
+                                     [[0, 0, 1, 0],
+                                      [0, 1, 1, 0],
+                                      [0, 1, 0, 0],
+                                      [0, 0, 0, 0]],
+
+                                     // South
+                                     [[0, 0, 0, 0],
+                                      [1, 1, 0, 0],
+                                      [0, 1, 1, 0],
+                                      [0, 0, 0, 0]],
+
+                                     // West
+                                     [[0, 0, 1, 0],
+                                      [0, 1, 1, 0],
+                                      [0, 1, 0, 0],
+                                      [0, 0, 0, 0]]];
+
+static LEFT_I_STATES: [Cells; 4] = [// North
+                                    [[0, 0, 0, 0],
+                                     [0, 0, 0, 0],
+                                     [1, 1, 1, 1],
+                                     [0, 0, 0, 0]],
+
+                                    // East
+                                    [[0, 1, 0, 0],
+                                     [0, 1, 0, 0],
+                                     [0, 1, 0, 0],
+                                     [0, 1, 0, 0]],
+
+                                    // South
+                                    [[0, 0, 0, 0],
+                                     [0, 0, 0, 0],
+                                     [1, 1, 1, 1],
+                                     [0, 0, 0, 0]],
+
+                                    // West
+                                    [[0, 1, 0, 0],
+                                     [0, 1, 0, 0],
+                                     [0, 1, 0, 0],
+                                     [0, 1, 0, 0]]];
+
+static LEFT_S_STATES: [Cells; 4] = [// North
+                                    [[0, 0, 0, 0],
+                                     [0, 1, 1, 0],
+                                     [1, 1, 0, 0],
+                                     [0, 0, 0, 0]],
+
+                                    // East
+                                    [[1, 0, 0, 0],
+                                     [1, 1, 0, 0],
+                                     [0, 1, 0, 0],
+                                     [0, 0, 0, 0]],
+
+                                    // South
+                                    [[0, 0, 0, 0],
+                                     [0, 1, 1, 0],
+                                     [1, 1, 0, 0],
+                                     [0, 0, 0, 0]],
+
+                                    // West
+                                    [[1, 0, 0, 0],
+                                     [1, 1, 0, 0],
+                                     [0, 1, 0, 0],
+                                     [0, 0, 0, 0]]];
+
+static LEFT_Z_STATES: [Cells; 4] = [// North
+                                    [[0, 0, 0, 0],
+                                     [1, 1, 0, 0],
+                                     [0, 1, 1, 0],
+                                     [0, 0, 0, 0]],
+
+                                    // East
+                                    [[0, 1, 0, 0],
+                                     [1, 1, 0, 0],
+                                     [1, 0, 0, 0],
+                                     [0, 0, 0, 0]],
+
+                                    // South
+                                    [[0, 0, 0, 0],
+                                     [1, 1, 0, 0],
+                                     [0, 1, 1, 0],
+                                     [0, 0, 0, 0]],
+
+                                    // West
+                                    [[0, 1, 0, 0],
+                                     [1, 1, 0, 0],
+                                     [1, 0, 0, 0],
+                                     [0, 0, 0, 0]]];
@@ -0,0 +1,35 @@
+use config::Config;
+use grid::Grid;
+use tetromino::{Direction, Tetromino, TetrominoKind};
+
+/// The cells a piece occupies within its 4x4 bounding box, indexed as
+/// `cells[y][x]`. Any non-zero entry is occupied.
+pub type Cells = [[u8; 4]; 4];
+
+/// A RotationSystem decides what each piece looks like in every
+/// orientation, where it spawns and how it is kicked when a rotation
+/// doesn't fit where it is.
+pub trait RotationSystem {
+    /// The cells of a piece in North, East, South, West order. Rotating
+    /// clockwise moves one step forward through this list.
+    fn states(&self, kind: TetrominoKind) -> [Cells; 4];
+
+    /// The orientation a piece has when it spawns
+    fn spawn_direction(&self, _kind: TetrominoKind) -> Direction {
+        Direction::North
+    }
+
+    /// Where the top-left corner of a piece's bounding box is placed
+    /// when it spawns
+    fn spawn_position(&self, kind: TetrominoKind, config: &Config) -> (i32, i32);
+
+    /// The offsets to try, in order, when `tetromino`, a tetromino of the
+    /// given kind, rotates to face `to`. The first entry should be (0, 0)
+    /// unless the rotation system never allows an unkicked rotation.
+    fn kicks(&self,
+             kind: TetrominoKind,
+             tetromino: &Tetromino,
+             to: Direction,
+             grid: &Grid)
+             -> Vec<(i32, i32)>;
+}
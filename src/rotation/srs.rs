@@ -0,0 +1,299 @@
+use config::Config;
+use grid::Grid;
+use rotation::{Cells, RotationSystem};
+use tetromino::{Direction, Tetromino, TetrominoKind};
+
+/// The Super Rotation System used by guideline Tetris games. Pieces
+/// spawn flat side down in the hidden rows and are kicked using the
+/// standard JLSTZ and I kick tables.
+pub struct SuperRotationSystem;
+
+impl RotationSystem for SuperRotationSystem {
+    fn states(&self, kind: TetrominoKind) -> [Cells; 4] {
+        match kind {
+            TetrominoKind::I => I_STATES,
+            TetrominoKind::J => J_STATES,
+            TetrominoKind::L => L_STATES,
+            TetrominoKind::O => O_STATES,
+            TetrominoKind::S => S_STATES,
+            TetrominoKind::T => T_STATES,
+            TetrominoKind::Z => Z_STATES,
+        }
+    }
+
+    fn spawn_position(&self, _kind: TetrominoKind, config: &Config) -> (i32, i32) {
+        // Pieces spawn in the bottom two hidden rows
+        ((config.grid_size.0 as i32 / 2) - 2, config.hidden_rows as i32 - 2)
+    }
+
+    fn kicks(&self,
+             kind: TetrominoKind,
+             tetromino: &Tetromino,
+             to: Direction,
+             _grid: &Grid)
+             -> Vec<(i32, i32)> {
+        kicks(kind, tetromino.direction(), to).to_vec()
+    }
+}
+
+// Kick offsets for the Super Rotation System. Offsets are (x, y) with y
+// growing downwards to match the grid, so they are the published tables
+// with the y axis flipped. The first entry is always "no kick".
+
+static JLSTZ_KICKS: [[(i32, i32); 5]; 8] = [
+    // North -> East
+    [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],
+    // East -> North
+    [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],
+    // East -> South
+    [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],
+    // South -> East
+    [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],
+    // South -> West
+    [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],
+    // West -> South
+    [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)],
+    // West -> North
+    [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)],
+    // North -> West
+    [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],
+];
+
+static I_KICKS: [[(i32, i32); 5]; 8] = [
+    // North -> East
+    [(0, 0), (-2, 0), (1, 0), (-2, 1), (1, -2)],
+    // East -> North
+    [(0, 0), (2, 0), (-1, 0), (2, -1), (-1, 2)],
+    // East -> South
+    [(0, 0), (-1, 0), (2, 0), (-1, -2), (2, 1)],
+    // South -> East
+    [(0, 0), (1, 0), (-2, 0), (1, 2), (-2, -1)],
+    // South -> West
+    [(0, 0), (2, 0), (-1, 0), (2, -1), (-1, 2)],
+    // West -> South
+    [(0, 0), (-2, 0), (1, 0), (-2, 1), (1, -2)],
+    // West -> North
+    [(0, 0), (1, 0), (-2, 0), (1, 2), (-2, -1)],
+    // North -> West
+    [(0, 0), (-1, 0), (2, 0), (-1, -2), (2, 1)],
+];
+
+static NO_KICKS: [(i32, i32); 1] = [(0, 0)];
+
+/// Returns the offsets to try, in order, when rotating a piece of the
+/// given kind from one orientation to another
+fn kicks(kind: TetrominoKind, from: Direction, to: Direction) -> &'static [(i32, i32)] {
+    let table = match kind {
+        TetrominoKind::I => &I_KICKS,
+        TetrominoKind::O => return &NO_KICKS,
+        _ => &JLSTZ_KICKS,
+    };
+
+    match (from, to) {
+        (Direction::North, Direction::East) => &table[0],
+        (Direction::East, Direction::North) => &table[1],
+        (Direction::East, Direction::South) => &table[2],
+        (Direction::South, Direction::East) => &table[3],
+        (Direction::South, Direction::West) => &table[4],
+        (Direction::West, Direction::South) => &table[5],
+        (Direction::West, Direction::North) => &table[6],
+        (Direction::North, Direction::West) => &table[7],
+        _ => &NO_KICKS,
+    }
+}
+
+static I_STATES: [Cells; 4] = [// North
+                               [[0, 0, 0, 0],
+                                [1, 1, 1, 1],
+                                [0, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[0, 0, 1, 0],
+                                [0, 0, 1, 0],
+                                [0, 0, 1, 0],
+                                [0, 0, 1, 0]],
+
+                               // South
+                               [[0, 0, 0, 0],
+                                [0, 0, 0, 0],
+                                [1, 1, 1, 1],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[0, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 1, 0, 0]]];
+
+static J_STATES: [Cells; 4] = [// North
+                               [[1, 0, 0, 0],
+                                [1, 1, 1, 0],
+                                [0, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[0, 1, 1, 0],
+                                [0, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // South
+                               [[0, 0, 0, 0],
+                                [1, 1, 1, 0],
+                                [0, 0, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[0, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [1, 1, 0, 0],
+                                [0, 0, 0, 0]]];
+
+static L_STATES: [Cells; 4] = [// North
+                               [[0, 0, 1, 0],
+                                [1, 1, 1, 0],
+                                [0, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[0, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // South
+                               [[0, 0, 0, 0],
+                                [1, 1, 1, 0],
+                                [1, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[1, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]]];
+
+static O_STATES: [Cells; 4] = [// North
+                               [[0, 1, 1, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[0, 1, 1, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // South
+                               [[0, 1, 1, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[0, 1, 1, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0],
+                                [0, 0, 0, 0]]];
+
+static S_STATES: [Cells; 4] = [// North
+                               [[0, 1, 1, 0],
+                                [1, 1, 0, 0],
+                                [0, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[0, 1, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // South
+                               [[0, 0, 0, 0],
+                                [0, 1, 1, 0],
+                                [1, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[1, 0, 0, 0],
+                                [1, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]]];
+
+static T_STATES: [Cells; 4] = [// North
+                               [[0, 1, 0, 0],
+                                [1, 1, 1, 0],
+                                [0, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[0, 1, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // South
+                               [[0, 0, 0, 0],
+                                [1, 1, 1, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[0, 1, 0, 0],
+                                [1, 1, 0, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]]];
+
+static Z_STATES: [Cells; 4] = [// North
+                               [[1, 1, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // East
+                               [[0, 0, 1, 0],
+                                [0, 1, 1, 0],
+                                [0, 1, 0, 0],
+                                [0, 0, 0, 0]],
+
+                               // South
+                               [[0, 0, 0, 0],
+                                [1, 1, 0, 0],
+                                [0, 1, 1, 0],
+                                [0, 0, 0, 0]],
+
+                               // West
+                               [[0, 1, 0, 0],
+                                [1, 1, 0, 0],
+                                [1, 0, 0, 0],
+                                [0, 0, 0, 0]]];
+
+#[cfg(test)]
+mod tests {
+    use super::kicks;
+    use tetromino::{Direction, TetrominoKind};
+
+    #[test]
+    fn jlstz_kicks_are_tried_in_guideline_order() {
+        assert_eq!(kicks(TetrominoKind::T, Direction::North, Direction::East),
+                   &[(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)]);
+        assert_eq!(kicks(TetrominoKind::S, Direction::West, Direction::North),
+                   &[(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)]);
+    }
+
+    #[test]
+    fn i_kicks_are_tried_in_guideline_order() {
+        assert_eq!(kicks(TetrominoKind::I, Direction::North, Direction::East),
+                   &[(0, 0), (-2, 0), (1, 0), (-2, 1), (1, -2)]);
+        assert_eq!(kicks(TetrominoKind::I, Direction::East, Direction::South),
+                   &[(0, 0), (-1, 0), (2, 0), (-1, -2), (2, 1)]);
+    }
+
+    #[test]
+    fn o_and_half_turns_are_not_kicked() {
+        assert_eq!(kicks(TetrominoKind::O, Direction::North, Direction::East), &[(0, 0)]);
+        assert_eq!(kicks(TetrominoKind::T, Direction::North, Direction::South), &[(0, 0)]);
+    }
+}
@@ -0,0 +1,20 @@
+
+use super::{Score, ScoreMetaData, ScoringSystem};
+
+pub struct DefaultScoringSystem;
+
+impl ScoringSystem for DefaultScoringSystem {
+    fn update_score(&self, score: &mut Score, metadata: ScoreMetaData) {
+        // 100 times the amount of lines that were cleared, with half as
+        // much again for back-to-back clears
+        let mut points = metadata.lines_cleared * 100;
+        if metadata.back_to_back {
+            points += points / 2;
+        }
+
+        // 50 for every clear in the current combo
+        points += metadata.combo * 50;
+
+        score.add(points + metadata.perfect_clear_bonus());
+    }
+}
\ No newline at end of file
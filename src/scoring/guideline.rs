@@ -0,0 +1,108 @@
+use std::cmp;
+
+use scoring::{Score, ScoreMetaData, ScoringSystem};
+use spin::TSpin;
+
+/// Scores clears the way the modern Tetris Guideline games do. Almost
+/// everything is multiplied by the level the clear happened on. Guideline
+/// games start on level 1, so level 0, which the classic presets start on,
+/// is scored as level 1 rather than scoring nothing.
+pub struct GuidelineScoringSystem;
+
+impl GuidelineScoringSystem {
+    /// The points for a clear before the level multiplier
+    fn clear_points(metadata: &ScoreMetaData) -> u32 {
+        match (metadata.t_spin, metadata.lines_cleared) {
+            (TSpin::None, 0) => 0,
+            (TSpin::None, 1) => 100,
+            (TSpin::None, 2) => 300,
+            (TSpin::None, 3) => 500,
+            (TSpin::None, _) => 800,
+            (TSpin::Mini, 0) => 100,
+            (TSpin::Mini, 1) => 200,
+            (TSpin::Mini, _) => 400,
+            (TSpin::Full, 0) => 400,
+            (TSpin::Full, 1) => 800,
+            (TSpin::Full, 2) => 1200,
+            (TSpin::Full, _) => 1600,
+        }
+    }
+}
+
+impl ScoringSystem for GuidelineScoringSystem {
+    fn update_score(&self, score: &mut Score, metadata: ScoreMetaData) {
+        let mut points = Self::clear_points(&metadata);
+
+        // Back-to-back clears are worth half as much again
+        if metadata.back_to_back {
+            points += points / 2;
+        }
+
+        points += metadata.combo * 50;
+        points += metadata.perfect_clear_bonus();
+
+        // Dropping is rewarded per cell, regardless of level
+        let drop_points = metadata.soft_drop_cells + metadata.hard_drop_cells * 2;
+
+        score.add(points * cmp::max(metadata.level, 1) + drop_points);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GuidelineScoringSystem;
+    use scoring::{Score, ScoreMetaData, ScoringSystem};
+    use spin::TSpin;
+
+    fn metadata(level: u32, lines_cleared: u32, t_spin: TSpin) -> ScoreMetaData {
+        ScoreMetaData {
+            level: level,
+            lines_cleared: lines_cleared,
+            t_spin: t_spin,
+            combo: 0,
+            back_to_back: false,
+            perfect_clear: false,
+            soft_drop_cells: 0,
+            hard_drop_cells: 0,
+        }
+    }
+
+    fn score(metadata: ScoreMetaData) -> u32 {
+        let mut score = Score::new();
+        GuidelineScoringSystem.update_score(&mut score, metadata);
+        score.score
+    }
+
+    #[test]
+    fn clears_are_multiplied_by_the_level() {
+        assert_eq!(score(metadata(1, 1, TSpin::None)), 100);
+        assert_eq!(score(metadata(1, 4, TSpin::None)), 800);
+        assert_eq!(score(metadata(3, 2, TSpin::Full)), 3600);
+        assert_eq!(score(metadata(2, 0, TSpin::Mini)), 200);
+    }
+
+    #[test]
+    fn level_zero_scores_as_level_one() {
+        assert_eq!(score(metadata(0, 4, TSpin::None)), 800);
+    }
+
+    #[test]
+    fn bonuses_are_added_before_the_level_multiplier() {
+        let mut tetris = metadata(2, 4, TSpin::None);
+        tetris.back_to_back = true;
+        tetris.combo = 2;
+        assert_eq!(score(tetris), (1200 + 100) * 2);
+
+        let mut perfect_clear = metadata(2, 1, TSpin::None);
+        perfect_clear.perfect_clear = true;
+        assert_eq!(score(perfect_clear), (100 + 800) * 2);
+    }
+
+    #[test]
+    fn drops_are_not_multiplied_by_the_level() {
+        let mut drop = metadata(10, 0, TSpin::None);
+        drop.soft_drop_cells = 3;
+        drop.hard_drop_cells = 5;
+        assert_eq!(score(drop), 13);
+    }
+}
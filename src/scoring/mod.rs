@@ -0,0 +1,44 @@
+use std::str::FromStr;
+
+mod default;
+mod guideline;
+mod nes;
+mod score;
+mod scoring_system;
+
+pub use scoring::score::Score;
+pub use scoring::scoring_system::{ScoreMetaData, ScoringSystem};
+pub use scoring::default::DefaultScoringSystem;
+pub use scoring::guideline::GuidelineScoringSystem;
+pub use scoring::nes::NesScoringSystem;
+
+/// The built in scoring systems that can be selected in the `Config`
+#[derive(Copy, Clone, PartialEq)]
+pub enum ScoringSystemType {
+    Default,
+    Guideline,
+    Nes,
+}
+
+impl ScoringSystemType {
+    pub fn create(&self) -> Box<ScoringSystem> {
+        match *self {
+            ScoringSystemType::Default => Box::new(DefaultScoringSystem),
+            ScoringSystemType::Guideline => Box::new(GuidelineScoringSystem),
+            ScoringSystemType::Nes => Box::new(NesScoringSystem),
+        }
+    }
+}
+
+impl FromStr for ScoringSystemType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ScoringSystemType, String> {
+        match &s.to_lowercase()[..] {
+            "default" => Ok(ScoringSystemType::Default),
+            "guideline" => Ok(ScoringSystemType::Guideline),
+            "nes" | "gameboy" | "gb" => Ok(ScoringSystemType::Nes),
+            _ => Err(format!("Unknown scoring system: {}", s)),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use scoring::{Score, ScoreMetaData, ScoringSystem};
+
+/// Scores clears the way NES and Game Boy Tetris do. Levels in those
+/// games start at 0, so clears are multiplied by one more than the level.
+pub struct NesScoringSystem;
+
+impl ScoringSystem for NesScoringSystem {
+    fn update_score(&self, score: &mut Score, metadata: ScoreMetaData) {
+        let points = match metadata.lines_cleared {
+            0 => 0,
+            1 => 40,
+            2 => 100,
+            3 => 300,
+            _ => 1200,
+        };
+
+        // Holding down earns a point for every row the piece was pushed
+        score.add(points * (metadata.level + 1) + metadata.soft_drop_cells);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NesScoringSystem;
+    use scoring::{Score, ScoreMetaData, ScoringSystem};
+    use spin::TSpin;
+
+    fn score(level: u32, lines_cleared: u32, soft_drop_cells: u32) -> u32 {
+        let mut score = Score::new();
+        NesScoringSystem.update_score(&mut score, ScoreMetaData {
+            level: level,
+            lines_cleared: lines_cleared,
+            t_spin: TSpin::None,
+            combo: 0,
+            back_to_back: false,
+            perfect_clear: false,
+            soft_drop_cells: soft_drop_cells,
+            hard_drop_cells: 0,
+        });
+        score.score
+    }
+
+    #[test]
+    fn clears_are_multiplied_by_one_more_than_the_level() {
+        assert_eq!(score(0, 1, 0), 40);
+        assert_eq!(score(0, 4, 0), 1200);
+        assert_eq!(score(9, 2, 0), 1000);
+        assert_eq!(score(18, 3, 0), 5700);
+        assert_eq!(score(19, 4, 0), 24000);
+    }
+
+    #[test]
+    fn soft_drops_are_not_multiplied() {
+        assert_eq!(score(5, 0, 12), 12);
+        assert_eq!(score(5, 1, 12), 252);
+    }
+}
@@ -0,0 +1,14 @@
+/// The running score of a game. It only ever increases.
+pub struct Score {
+    pub score: u32,
+}
+
+impl Score {
+    pub fn new() -> Score {
+        Score { score: 0 }
+    }
+
+    pub fn add(&mut self, value: u32) {
+        self.score += value;
+    }
+}
@@ -0,0 +1,43 @@
+use super::Score;
+use spin::TSpin;
+
+/// Decides how many points each locked piece is worth
+pub trait ScoringSystem {
+    fn update_score(&self, score: &mut Score, metadata: ScoreMetaData);
+}
+
+/// Everything a `ScoringSystem` knows about the piece that just locked
+#[derive(Copy, Clone)]
+pub struct ScoreMetaData {
+    /// The level the piece locked on, before any level up it causes
+    pub level: u32,
+    pub lines_cleared: u32,
+    pub t_spin: TSpin,
+    /// How many pieces in a row cleared lines immediately before this one
+    pub combo: u32,
+    /// Whether this is a Tetris or T-spin clear following another one
+    pub back_to_back: bool,
+    /// Whether the lines cleared left the grid completely empty
+    pub perfect_clear: bool,
+    /// How many rows the piece was soft dropped
+    pub soft_drop_cells: u32,
+    /// How many rows the piece was hard dropped
+    pub hard_drop_cells: u32,
+}
+
+impl ScoreMetaData {
+    /// The guideline bonus for a perfect clear, before any level multiplier
+    pub fn perfect_clear_bonus(&self) -> u32 {
+        if !self.perfect_clear {
+            return 0;
+        }
+
+        match self.lines_cleared {
+            1 => 800,
+            2 => 1200,
+            3 => 1800,
+            _ if self.back_to_back => 3200,
+            _ => 2000,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,176 @@
+use grid::Grid;
+use tetromino::{Rotation, Tetromino, TetrominoKind};
+
+/// The entry in the kick table that turns a mini T-spin into a full one.
+/// In SRS this is the final, 1x2 kick used by T-spin triples. 180 degree
+/// kick tables have no such kick.
+static FULL_SPIN_KICK: usize = 4;
+
+/// How a T piece was spun into the position it locked in
+#[derive(Copy, Clone, PartialEq)]
+pub enum TSpin {
+    None,
+    Mini,
+    Full,
+}
+
+/// Works out whether `tetromino` is locking as a T-spin, using the
+/// guideline 3-corner rule. `last_rotation` is the last rotation and the
+/// kick table entry it used, or `None` if the piece has moved since it was
+/// rotated.
+pub fn detect_t_spin(tetromino: &Tetromino,
+                     grid: &Grid,
+                     last_rotation: Option<(Rotation, usize)>)
+                     -> TSpin {
+    let (rotation, kick) = match last_rotation {
+        Some(last_rotation) if tetromino.kind == Some(TetrominoKind::T) => last_rotation,
+        _ => return TSpin::None,
+    };
+    let full_spin_kick = rotation != Rotation::Half && kick == FULL_SPIN_KICK;
+
+    let mut cells = Vec::new();
+    for (y, row) in tetromino.blocks.iter().enumerate() {
+        for (x, block) in row.iter().enumerate() {
+            if block.is_some() {
+                cells.push((x as i32, y as i32));
+            }
+        }
+    }
+
+    let sides = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+    // The centre of a T is the only cell touching the other three, and it
+    // points away from the one side it has no neighbour on
+    let centre = cells.iter().cloned().find(|&(x, y)| {
+        sides.iter().filter(|&&(dx, dy)| cells.contains(&(x + dx, y + dy))).count() == 3
+    });
+    let (cx, cy) = match centre {
+        Some(centre) => centre,
+        None => return TSpin::None,
+    };
+    let (fx, fy) = match sides.iter().find(|&&(dx, dy)| !cells.contains(&(cx + dx, cy + dy))) {
+        Some(&(dx, dy)) => (-dx, -dy),
+        None => return TSpin::None,
+    };
+
+    let occupied = |dx: i32, dy: i32| {
+        let x = tetromino.x + cx + dx;
+        let y = tetromino.y + cy + dy;
+
+        // The walls and floor count as occupied, the space above the grid
+        // does not
+        y >= 0 && grid.is_occupied(x, y)
+    };
+
+    let front = [occupied(fx - fy, fy + fx), occupied(fx + fy, fy - fx)];
+    let back = [occupied(-fx - fy, -fy + fx), occupied(-fx + fy, -fy - fx)];
+    let corners = front.iter().chain(back.iter()).filter(|&&corner| corner).count();
+
+    if corners < 3 {
+        TSpin::None
+    } else if front[0] && front[1] || full_spin_kick {
+        TSpin::Full
+    } else {
+        TSpin::Mini
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{detect_t_spin, TSpin};
+    use block::Block;
+    use config::ConfigBuilder;
+    use factory::TetrominoFactory;
+    use grid::Grid;
+    use piece_set::PieceSet;
+    use randomizer::RandomizerType;
+    use tetromino::{Rotation, Tetromino, TetrominoKind};
+
+    fn grid(rows: &[&str]) -> Grid {
+        let mut grid = Grid::new(rows[0].len(), rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, cell) in row.chars().enumerate() {
+                if cell == 'X' {
+                    grid.boxes[y][x] = Some(Block::new([1.0; 4]));
+                }
+            }
+        }
+        grid
+    }
+
+    /// A piece of the given kind pointing down, with its 3x3 box at (0, 1)
+    fn upside_down(kind: TetrominoKind) -> Tetromino {
+        let piece_set = PieceSet::tetrominoes();
+        let factory = TetrominoFactory::new(RandomizerType::SevenBag.create(0, &piece_set), 0);
+        let config = ConfigBuilder::new().build();
+
+        let mut tetromino = factory.build(piece_set.find(kind).unwrap(), &config, &piece_set);
+        tetromino.rotate(Rotation::Half, (0, 0));
+        tetromino.x = 0;
+        tetromino.y = 1;
+        tetromino
+    }
+
+    // Both corners under the point of the T are filled
+    static T_SPIN_SLOT: [&'static str; 4] = [".....",
+                                             "X....",
+                                             "...XX",
+                                             "X.XXX"];
+
+    // Only one corner under the point is filled, the other is open
+    static MINI_SLOT: [&'static str; 4] = [".....",
+                                           "X.X..",
+                                           "...XX",
+                                           "X..XX"];
+
+    #[test]
+    fn three_corners_with_both_in_front_is_a_t_spin() {
+        let t = upside_down(TetrominoKind::T);
+        let grid = grid(&T_SPIN_SLOT);
+        assert!(detect_t_spin(&t, &grid, Some((Rotation::Clockwise, 0))) == TSpin::Full);
+        assert!(detect_t_spin(&t, &grid, Some((Rotation::Clockwise, 2))) == TSpin::Full);
+    }
+
+    #[test]
+    fn three_corners_with_one_in_front_is_a_mini_t_spin() {
+        let t = upside_down(TetrominoKind::T);
+        let grid = grid(&MINI_SLOT);
+        assert!(detect_t_spin(&t, &grid, Some((Rotation::Clockwise, 0))) == TSpin::Mini);
+        assert!(detect_t_spin(&t, &grid, Some((Rotation::CounterClockwise, 3))) == TSpin::Mini);
+    }
+
+    #[test]
+    fn the_tst_kick_makes_a_mini_t_spin_full() {
+        let t = upside_down(TetrominoKind::T);
+        let grid = grid(&MINI_SLOT);
+        assert!(detect_t_spin(&t, &grid, Some((Rotation::Clockwise, 4))) == TSpin::Full);
+    }
+
+    #[test]
+    fn the_same_kick_in_a_half_turn_stays_a_mini_t_spin() {
+        let t = upside_down(TetrominoKind::T);
+        let grid = grid(&MINI_SLOT);
+        assert!(detect_t_spin(&t, &grid, Some((Rotation::Half, 4))) == TSpin::Mini);
+    }
+
+    #[test]
+    fn moving_after_rotating_is_not_a_t_spin() {
+        let t = upside_down(TetrominoKind::T);
+        let grid = grid(&T_SPIN_SLOT);
+        assert!(detect_t_spin(&t, &grid, None) == TSpin::None);
+    }
+
+    #[test]
+    fn two_corners_is_not_a_t_spin() {
+        let t = upside_down(TetrominoKind::T);
+        let grid = grid(&[".....", ".....", "...XX", "X.XXX"]);
+        assert!(detect_t_spin(&t, &grid, Some((Rotation::Clockwise, 0))) == TSpin::None);
+    }
+
+    #[test]
+    fn only_t_pieces_spin() {
+        let j = upside_down(TetrominoKind::J);
+        let grid = grid(&T_SPIN_SLOT);
+        assert!(detect_t_spin(&j, &grid, Some((Rotation::Clockwise, 0))) == TSpin::None);
+    }
+}
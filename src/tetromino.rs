@@ -0,0 +1,342 @@
+
+use block::Block;
+use grid::Grid;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+pub enum MoveResult {
+    Allow,
+    Deny,
+    Blocked,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum Rotation {
+    Clockwise,
+    CounterClockwise,
+    /// A 180 degree turn
+    Half,
+}
+
+pub enum RotationResult {
+    /// The rotation fits once the piece is moved by `offset`, which is
+    /// entry number `kick` in the kick table (0 meaning it was not kicked)
+    Allow { kick: usize, offset: (i32, i32) },
+    Deny,
+}
+
+/// The seven one-sided Tetrominos
+#[derive(Copy, Clone, PartialEq)]
+pub enum TetrominoKind {
+    I,
+    J,
+    L,
+    O,
+    S,
+    T,
+    Z,
+}
+
+impl TetrominoKind {
+    /// Every kind of Tetromino, in alphabetical order
+    pub fn all() -> [TetrominoKind; 7] {
+        [TetrominoKind::I,
+         TetrominoKind::J,
+         TetrominoKind::L,
+         TetrominoKind::O,
+         TetrominoKind::S,
+         TetrominoKind::T,
+         TetrominoKind::Z]
+    }
+}
+
+/// The blocks of a piece in one orientation, indexed as `blocks[y][x]`
+pub type Blocks = Vec<Vec<Option<Block>>>;
+
+/// A falling piece. `x` and `y` are the grid co-ordinates of the top-left
+/// corner of its square bounding box, so they may be negative or extend
+/// past the edge of the grid as long as no occupied cell does. `blocks`
+/// always holds the cells for the current orientation and `shadow` the
+/// matching cells for its landing preview.
+#[derive(Clone)]
+pub struct Tetromino {
+    /// The index of this piece in the engine's `PieceSet`
+    pub piece: usize,
+    /// Which tetromino this is, if the piece is one
+    pub kind: Option<TetrominoKind>,
+    pub x: i32,
+    pub y: i32,
+    pub blocks: Blocks,
+    pub shadow: Blocks,
+    north: Blocks,
+    east: Blocks,
+    south: Blocks,
+    west: Blocks,
+    shadow_north: Blocks,
+    shadow_east: Blocks,
+    shadow_south: Blocks,
+    shadow_west: Blocks,
+    direction: Direction,
+}
+
+/// The four orientations of a piece, in North, East, South, West order
+pub struct TetrominoShape(pub Blocks, pub Blocks, pub Blocks, pub Blocks);
+
+impl Tetromino {
+    pub fn new(piece: usize,
+               kind: Option<TetrominoKind>,
+               shape: TetrominoShape,
+               shadow: TetrominoShape,
+               position: (i32, i32),
+               direction: Direction)
+               -> Tetromino {
+        let mut tetromino = Tetromino {
+            piece: piece,
+            kind: kind,
+            x: position.0,
+            y: position.1,
+            blocks: Vec::new(),
+            shadow: Vec::new(),
+            north: shape.0,
+            east: shape.1,
+            south: shape.2,
+            west: shape.3,
+            shadow_north: shadow.0,
+            shadow_east: shadow.1,
+            shadow_south: shadow.2,
+            shadow_west: shadow.3,
+            direction: direction,
+        };
+
+        tetromino.blocks = tetromino.get_blocks_for_direction(direction, None);
+        tetromino.shadow = tetromino.get_blocks_for_direction(direction, true);
+        tetromino
+    }
+
+    /// The direction this Tetromino is currently facing
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Checks if the current block can move in a specific
+    /// direction.
+    pub fn can_move(&self, direction: Direction, grid: &Grid) -> MoveResult {
+
+        // Determine the direction on each axis we're attempting to move
+        let y_dir = Self::get_y_direction(direction) as i32;
+        let x_dir = Self::get_x_direction(direction) as i32;
+
+        // Loop over each block of this tetromino and compare it
+        // to the offset within the grid where we want to move to
+        for (y, row) in self.blocks.iter().enumerate() {
+            for (x, block) in row.iter().enumerate() {
+                if block.is_some() {
+                    let x = (self.x + x as i32) + x_dir;
+                    let y = (self.y + y as i32) + y_dir;
+
+                    // Check if we've hit the bottom
+                    if y >= grid.height() as i32 {
+                        return MoveResult::Blocked;
+                    }
+
+                    // Check if we're touching the edges
+                    if x < 0 || x >= grid.width() as i32 {
+                        return MoveResult::Deny;
+                    }
+
+                    // Anything above the grid is open
+                    if y < 0 {
+                        continue;
+                    }
+
+                    // Otherwise check if we're smashing in to another block
+                    if grid.is_occupied(x, y) {
+                        // Deny left and right.. but block downwards
+                        match direction {
+                            Direction::East | Direction::West => return MoveResult::Deny,
+                            Direction::North | Direction::South => return MoveResult::Blocked,
+                        }
+                    }
+                }
+            }
+        }
+
+        MoveResult::Allow
+    }
+
+    /// Checks if the current tetromino can rotate in its given position,
+    /// trying each of the given kicks in turn
+    pub fn can_rotate(&self,
+                      rotation: Rotation,
+                      grid: &Grid,
+                      kicks: &[(i32, i32)])
+                      -> RotationResult {
+
+        let new_dir = self.get_rotated_position(rotation);
+        let desired_blocks = self.get_blocks_for_direction(new_dir, None);
+
+        // Apply the first kick that doesn't smash in to a wall, the floor
+        // or another block
+        for (kick, &(x_offset, y_offset)) in kicks.iter().enumerate() {
+            if Self::fits(&desired_blocks, self.x + x_offset, self.y + y_offset, grid) {
+                return RotationResult::Allow {
+                    kick: kick,
+                    offset: (x_offset, y_offset),
+                };
+            }
+        }
+
+        RotationResult::Deny
+    }
+
+    /// Checks whether the given blocks fit in the grid with their top-left
+    /// corner at the given co-ordinates
+    fn fits(blocks: &Blocks,
+            grid_x: i32,
+            grid_y: i32,
+            grid: &Grid)
+            -> bool {
+        for (y, y_block) in blocks.iter().enumerate() {
+            for (x, x_block) in y_block.iter().enumerate() {
+                if x_block.is_some() {
+                    let x = grid_x + x as i32;
+                    let y = grid_y + y as i32;
+
+                    // Anything above the grid is open, as long as it is
+                    // between the walls
+                    if y < 0 && x >= 0 && x < grid.width() as i32 {
+                        continue;
+                    }
+
+                    // Check if we will hit the bottom or edges, or smash in
+                    // to another block
+                    if grid.is_occupied(x, y) {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Whether the Tetromino overlaps the walls, the floor or the stack
+    /// where it is
+    pub fn overlaps(&self, grid: &Grid) -> bool {
+        !Self::fits(&self.blocks, self.x, self.y, grid)
+    }
+
+    /// Tests a cloned Tetromino to find where this current Tetromino
+    /// will land
+    pub fn find_landing_xy(&self, grid: &Grid) -> (i32, i32) {
+        let mut clone = self.clone();
+
+        loop {
+            if let MoveResult::Allow = clone.can_move(Direction::South, grid) {
+                clone.drop_down();
+            } else {
+                return (clone.x, clone.y);
+            }
+        }
+    }
+
+    /// Determines the direction this Tetromino would face after turning
+    /// in the given direction
+    pub fn get_rotated_position(&self, direction: Rotation) -> Direction {
+        match direction {
+            Rotation::Clockwise => {
+                match self.direction {
+                    Direction::North => Direction::East,
+                    Direction::East => Direction::South,
+                    Direction::South => Direction::West,
+                    Direction::West => Direction::North,
+                }
+            }
+            Rotation::CounterClockwise => {
+                match self.direction {
+                    Direction::North => Direction::West,
+                    Direction::West => Direction::South,
+                    Direction::South => Direction::East,
+                    Direction::East => Direction::North,
+                }
+            }
+            Rotation::Half => {
+                match self.direction {
+                    Direction::North => Direction::South,
+                    Direction::East => Direction::West,
+                    Direction::South => Direction::North,
+                    Direction::West => Direction::East,
+                }
+            }
+        }
+    }
+
+    /// Rotates the tetromino, moving it by the offset returned from
+    /// `can_rotate`
+    pub fn rotate(&mut self, rotation: Rotation, offset: (i32, i32)) {
+        let new_dir = self.get_rotated_position(rotation);
+        self.x += offset.0;
+        self.y += offset.1;
+        self.blocks = self.get_blocks_for_direction(new_dir, None);
+        self.shadow = self.get_blocks_for_direction(new_dir, true);
+        self.direction = new_dir;
+    }
+
+    fn get_blocks_for_direction<O>(&self,
+                                   direction: Direction,
+                                   shadow: O)
+                                   -> Blocks
+        where O: Into<Option<bool>>
+    {
+        let s = shadow.into();
+        if s.is_some() && s.unwrap() {
+            match direction {
+                Direction::North => self.shadow_north.clone(),
+                Direction::East => self.shadow_east.clone(),
+                Direction::South => self.shadow_south.clone(),
+                Direction::West => self.shadow_west.clone(),
+            }
+        } else {
+            match direction {
+                Direction::North => self.north.clone(),
+                Direction::East => self.east.clone(),
+                Direction::South => self.south.clone(),
+                Direction::West => self.west.clone(),
+            }
+        }
+    }
+
+    fn get_y_direction(direction: Direction) -> isize {
+        match direction {
+            Direction::North => -1,
+            Direction::South => 1,
+            _ => 0,
+        }
+    }
+
+    fn get_x_direction(direction: Direction) -> isize {
+        match direction {
+            Direction::East => 1,
+            Direction::West => -1,
+            _ => 0,
+        }
+    }
+
+    pub fn drop_down(&mut self) {
+        self.y += 1;
+    }
+
+    pub fn move_left(&mut self) {
+        self.x -= 1;
+    }
+
+    pub fn move_right(&mut self) {
+        self.x += 1;
+    }
+}